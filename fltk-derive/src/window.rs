@@ -21,6 +21,10 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
     );
     let icon = Ident::new(format!("{}_{}", name_str, "icon").as_str(), name.span());
     let set_icon = Ident::new(format!("{}_{}", name_str, "set_icon").as_str(), name.span());
+    let set_icons = Ident::new(
+        format!("{}_{}", name_str, "set_icons").as_str(),
+        name.span(),
+    );
     let set_border = Ident::new(
         format!("{}_{}", name_str, "set_border").as_str(),
         name.span(),
@@ -62,6 +66,26 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
         name.span(),
     );
     let hotspot = Ident::new(format!("{}_{}", name_str, "hotspot").as_str(), name.span());
+    let request_attention = Ident::new(
+        format!("{}_{}", name_str, "request_attention").as_str(),
+        name.span(),
+    );
+    let fullscreen_screens = Ident::new(
+        format!("{}_{}", name_str, "fullscreen_screens").as_str(),
+        name.span(),
+    );
+    let move_to_screen = Ident::new(
+        format!("{}_{}", name_str, "move_to_screen").as_str(),
+        name.span(),
+    );
+    let set_ime_allowed = Ident::new(
+        format!("{}_{}", name_str, "set_ime_allowed").as_str(),
+        name.span(),
+    );
+    let set_ime_cursor_area = Ident::new(
+        format!("{}_{}", name_str, "set_ime_cursor_area").as_str(),
+        name.span(),
+    );
 
     let gen = quote! {
         unsafe impl HasRawWindowHandle for #name {
@@ -153,7 +177,6 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
 
             fn set_icon<T: ImageExt>(&mut self, image: Option<T>) {
                 assert!(!self.was_deleted());
-                assert!(std::any::type_name::<T>() != std::any::type_name::<crate::image::SharedImage>(), "SharedImage icons are not supported!");
                 assert!(std::any::type_name::<T>() != std::any::type_name::<crate::image::Pixmap>(), "Pixmap icons are not supported!");
                 assert!(std::any::type_name::<T>() != std::any::type_name::<crate::image::XpmImage>(), "Xpm icons are not supported!");
                 assert!(std::any::type_name::<T>() != std::any::type_name::<crate::image::XbmImage>(), "Xbm icons are not supported!");
@@ -170,6 +193,19 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn set_icons<T: ImageExt>(&mut self, images: &[T]) {
+                assert!(!self.was_deleted());
+                let mut ptrs: Vec<*mut raw::c_void> = Vec::with_capacity(images.len());
+                for image in images {
+                    assert!(!image.was_deleted());
+                    unsafe { image.increment_arc(); }
+                    ptrs.push(image.as_image_ptr() as *mut _);
+                }
+                unsafe {
+                    #set_icons(self._inner, ptrs.as_ptr() as *mut *mut raw::c_void, ptrs.len() as i32)
+                }
+            }
+
             fn set_cursor(&mut self, cursor: Cursor) {
                 assert!(!self.was_deleted());
                 unsafe {
@@ -301,6 +337,41 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
                     #hotspot(self._inner, w.as_widget_ptr() as _)
                 }
             }
+
+            fn request_attention(&mut self, critical: bool) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #request_attention(self._inner, critical as i32)
+                }
+            }
+
+            fn fullscreen_screens(&mut self, top: i32, bottom: i32, left: i32, right: i32) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #fullscreen_screens(self._inner, top, bottom, left, right)
+                }
+            }
+
+            fn move_to_screen(&mut self, idx: i32) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #move_to_screen(self._inner, idx)
+                }
+            }
+
+            fn set_ime_allowed(&mut self, allowed: bool) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #set_ime_allowed(self._inner, allowed as i32)
+                }
+            }
+
+            fn set_ime_cursor_area(&mut self, x: i32, y: i32, w: i32, h: i32) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #set_ime_cursor_area(self._inner, x, y, w, h)
+                }
+            }
         }
     };
     gen.into()
@@ -63,6 +63,24 @@ extern "C" {
 extern "C" {
     pub fn Fl_Widget_deactivate(arg1: *mut Fl_Widget);
 }
+extern "C" {
+    pub fn Fl_Widget_output(arg1: *const Fl_Widget) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Widget_set_output(arg1: *mut Fl_Widget);
+}
+extern "C" {
+    pub fn Fl_Widget_clear_output(arg1: *mut Fl_Widget);
+}
+extern "C" {
+    pub fn Fl_Widget_active(arg1: *const Fl_Widget) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Widget_active_r(arg1: *const Fl_Widget) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Widget_set_active(arg1: *mut Fl_Widget);
+}
 extern "C" {
     pub fn Fl_Widget_redraw_label(arg1: *mut Fl_Widget);
 }
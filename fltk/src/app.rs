@@ -0,0 +1,139 @@
+pub use crate::prelude::*;
+use std::sync::Mutex;
+
+/// The system color scheme reported by the host platform
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Theme {
+    /// Light appearance
+    Light,
+    /// Dark appearance
+    Dark,
+}
+
+static THEME_CALLBACK: Mutex<Option<Box<dyn FnMut(Theme) + Send>>> = Mutex::new(None);
+
+/// Queries the host's current color scheme.
+/// Internally this reads `AppearsDarkAqua` on macOS, the `AppsUseLightTheme`
+/// registry value on Windows, and the `org.freedesktop.appearance color-scheme`
+/// portal (falling back to `gsettings`) on Linux.
+pub fn system_theme() -> Theme {
+    unsafe {
+        if Fl_system_theme() == 1 {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
+    }
+}
+
+/// Installs a callback fired when the OS appearance changes, so `App`-level
+/// redraws can re-theme widgets automatically. Each call replaces any previous
+/// callback and installs the platform change listener again.
+pub fn set_theme_callback<F: FnMut(Theme) + Send + 'static>(cb: F) {
+    *THEME_CALLBACK.lock().unwrap() = Some(Box::new(cb));
+    unsafe {
+        Fl_install_theme_listener(Some(theme_change_shim));
+    }
+}
+
+extern "C" fn theme_change_shim(dark: i32) {
+    let theme = if dark == 1 { Theme::Dark } else { Theme::Light };
+    if let Some(cb) = THEME_CALLBACK.lock().unwrap().as_mut() {
+        cb(theme);
+    }
+}
+
+/// Geometry and metrics for a single physical screen, as reported by the
+/// platform's multi-monitor APIs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Screen {
+    /// Zero-based screen index
+    pub index: i32,
+    /// Full bounding box `(x, y, w, h)` of the screen in global coordinates
+    pub bounds: (i32, i32, i32, i32),
+    /// Usable work area `(x, y, w, h)`, excluding taskbars/docks
+    pub work_area: (i32, i32, i32, i32),
+    /// Horizontal and vertical dots-per-inch
+    pub dpi: (f32, f32),
+    /// Whether this is the primary screen (index 0)
+    pub primary: bool,
+}
+
+/// Returns the number of screens connected to the host.
+pub fn screen_count() -> i32 {
+    unsafe { Fl_screen_count() }
+}
+
+/// Enumerates every connected screen with its bounding box, usable work area,
+/// DPI, and whether it is the primary display.
+pub fn screens() -> Vec<Screen> {
+    let mut out = Vec::new();
+    unsafe {
+        for index in 0..Fl_screen_count() {
+            let (mut x, mut y, mut w, mut h) = (0, 0, 0, 0);
+            Fl_screen_xywh(&mut x, &mut y, &mut w, &mut h, index);
+            let (mut wx, mut wy, mut ww, mut wh) = (0, 0, 0, 0);
+            Fl_screen_work_area(&mut wx, &mut wy, &mut ww, &mut wh, index);
+            let (mut dh, mut dv) = (0.0f32, 0.0f32);
+            Fl_screen_dpi(&mut dh, &mut dv, index);
+            out.push(Screen {
+                index,
+                bounds: (x, y, w, h),
+                work_area: (wx, wy, ww, wh),
+                dpi: (dh, dv),
+                primary: index == 0,
+            });
+        }
+    }
+    out
+}
+
+/// Safe wrappers over the `Fl_Widget` output and active-state flags, available
+/// on every widget through a blanket implementation. `output` keeps a widget
+/// drawn enabled-looking (it still shows selection colors) but treats it as
+/// read-only, rejecting focus and events — distinct from the grayed-out state
+/// produced by `deactivate`.
+pub trait WidgetOutputExt: WidgetExt {
+    /// Returns whether the widget is flagged output-only
+    fn output(&self) -> bool;
+    /// Marks the widget output-only (drawable but non-interactive)
+    fn set_output(&mut self);
+    /// Clears the output-only flag
+    fn clear_output(&mut self);
+    /// Returns whether the widget's own active flag is set
+    fn active(&self) -> bool;
+    /// Returns whether the widget is active, walking up its parents
+    fn active_r(&self) -> bool;
+    /// Sets the widget's active flag without recursing into children
+    fn set_active(&mut self);
+}
+
+impl<T: WidgetExt> WidgetOutputExt for T {
+    fn output(&self) -> bool {
+        unsafe { fltk_sys::widget::Fl_Widget_output(self.as_widget_ptr()) != 0 }
+    }
+    fn set_output(&mut self) {
+        unsafe { fltk_sys::widget::Fl_Widget_set_output(self.as_widget_ptr()) }
+    }
+    fn clear_output(&mut self) {
+        unsafe { fltk_sys::widget::Fl_Widget_clear_output(self.as_widget_ptr()) }
+    }
+    fn active(&self) -> bool {
+        unsafe { fltk_sys::widget::Fl_Widget_active(self.as_widget_ptr()) != 0 }
+    }
+    fn active_r(&self) -> bool {
+        unsafe { fltk_sys::widget::Fl_Widget_active_r(self.as_widget_ptr()) != 0 }
+    }
+    fn set_active(&mut self) {
+        unsafe { fltk_sys::widget::Fl_Widget_set_active(self.as_widget_ptr()) }
+    }
+}
+
+extern "C" {
+    fn Fl_system_theme() -> i32;
+    fn Fl_install_theme_listener(cb: Option<extern "C" fn(i32)>);
+    fn Fl_screen_count() -> i32;
+    fn Fl_screen_xywh(x: *mut i32, y: *mut i32, w: *mut i32, h: *mut i32, n: i32);
+    fn Fl_screen_work_area(x: *mut i32, y: *mut i32, w: *mut i32, h: *mut i32, n: i32);
+    fn Fl_screen_dpi(h: *mut f32, v: *mut f32, n: i32);
+}
@@ -378,3 +378,572 @@ impl CheckBrowser {
         }
     }
 }
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug)]
+struct TreeNode {
+    text: String,
+    depth: i32,
+    open: bool,
+}
+
+/// A hierarchical browser built on the `depth` field idiom used by FLUID's
+/// widget browser: each item carries a depth, rows are indented by
+/// `depth * indent_px`, and a node whose following item is deeper gets an
+/// expand/collapse arrow. Collapsing hides the following deeper items until the
+/// depth returns to the node's level. Selection and column support from the
+/// underlying browser continue to work on the visible rows.
+#[derive(Clone)]
+pub struct TreeBrowser {
+    inner: HoldBrowser,
+    model: Rc<RefCell<Vec<TreeNode>>>,
+    indent_px: i32,
+}
+
+impl std::fmt::Debug for TreeBrowser {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("TreeBrowser").finish()
+    }
+}
+
+impl std::ops::Deref for TreeBrowser {
+    type Target = HoldBrowser;
+    fn deref(&self) -> &HoldBrowser {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for TreeBrowser {
+    fn deref_mut(&mut self) -> &mut HoldBrowser {
+        &mut self.inner
+    }
+}
+
+impl TreeBrowser {
+    /// Creates a new tree browser
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: Option<&str>) -> TreeBrowser {
+        let inner = HoldBrowser::new(x, y, w, h, label);
+        let mut tree = TreeBrowser {
+            inner,
+            model: Rc::new(RefCell::new(vec![])),
+            indent_px: 16,
+        };
+        let model = tree.model.clone();
+        let indent = tree.indent_px;
+        tree.inner.handle(move |b, ev| {
+            if ev == Event::Released {
+                let line = b.value();
+                if line > 0 {
+                    toggle_if_parent(b, &model, indent, line as usize);
+                }
+            }
+            false
+        });
+        tree
+    }
+
+    /// Adds an item at the given `depth`, returning its model index
+    pub fn add_with_depth(&mut self, text: &str, depth: i32) -> usize {
+        self.model.borrow_mut().push(TreeNode {
+            text: text.to_string(),
+            depth,
+            open: true,
+        });
+        self.render();
+        self.model.borrow().len() - 1
+    }
+
+    /// Opens or closes the node at the given model `line` (1-based)
+    pub fn set_item_open(&mut self, line: usize, open: bool) {
+        if let Some(node) = self.model.borrow_mut().get_mut(line - 1) {
+            node.open = open;
+        }
+        self.render();
+    }
+
+    /// Returns whether the node at `line` (1-based) is open
+    pub fn is_open(&self, line: usize) -> bool {
+        self.model
+            .borrow()
+            .get(line - 1)
+            .map(|n| n.open)
+            .unwrap_or(false)
+    }
+
+    fn render(&mut self) {
+        render_tree(&mut self.inner, &self.model.borrow(), self.indent_px);
+    }
+}
+
+/// Whether node at model index `i` has children (a following deeper item)
+fn has_children(model: &[TreeNode], i: usize) -> bool {
+    model
+        .get(i + 1)
+        .map(|n| n.depth > model[i].depth)
+        .unwrap_or(false)
+}
+
+/// Rebuilds the visible rows from the model, skipping items hidden under a
+/// collapsed ancestor, indenting by depth, and prefixing an arrow on parents.
+fn render_tree(b: &mut HoldBrowser, model: &[TreeNode], indent: i32) {
+    b.clear();
+    let mut i = 0;
+    while i < model.len() {
+        let node = &model[i];
+        let arrow = if has_children(model, i) {
+            if node.open {
+                "@-32->"
+            } else {
+                "@-32>"
+            }
+        } else {
+            "  "
+        };
+        let pad = " ".repeat(((node.depth * indent) / 4).max(0) as usize);
+        b.add(&format!("{}{} {}", pad, arrow, node.text));
+        if !node.open {
+            // skip the collapsed subtree
+            let level = node.depth;
+            i += 1;
+            while i < model.len() && model[i].depth > level {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn toggle_if_parent(
+    b: &mut HoldBrowser,
+    model: &Rc<RefCell<Vec<TreeNode>>>,
+    indent: i32,
+    visible_line: usize,
+) {
+    // map the visible line back to a model index, accounting for hidden subtrees
+    let mut m = model.borrow_mut();
+    let mut vis = 0usize;
+    let mut i = 0usize;
+    while i < m.len() {
+        vis += 1;
+        if vis == visible_line {
+            if has_children(&m, i) {
+                m[i].open = !m[i].open;
+                let snapshot = m.clone();
+                drop(m);
+                render_tree(b, &snapshot, indent);
+                b.do_callback();
+            }
+            return;
+        }
+        if !m[i].open {
+            let level = m[i].depth;
+            i += 1;
+            while i < m.len() && m[i].depth > level {
+                i += 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+}
+
+use std::sync::Mutex;
+use std::collections::BTreeMap;
+
+/// Marquee auto-scroll mode for over-long browser rows
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MarqueeMode {
+    /// No marquee scrolling
+    Off,
+    /// Scroll the selected row when it overflows the width, then restart from
+    /// its beginning
+    Enabled,
+}
+
+struct MarqueeState {
+    counter: i32,
+    delay: i32,
+    last_line: i32,
+}
+
+// Per-widget marquee state, keyed by raw widget pointer.
+static MARQUEE: Mutex<BTreeMap<usize, MarqueeState>> = Mutex::new(BTreeMap::new());
+
+impl Browser {
+    /// Enables or disables marquee auto-scroll of the selected row. When the
+    /// selected row's pixel width exceeds the widget width it is scrolled
+    /// horizontally one pixel per ~333 ms tick, after a short start delay, until
+    /// its end is visible, and then jumps back to the start; the offset resets
+    /// whenever the selection changes or the row fits.
+    pub fn set_marquee(&mut self, mode: MarqueeMode) {
+        assert!(!self.was_deleted());
+        let key = self._inner as usize;
+        if mode == MarqueeMode::Off {
+            MARQUEE.lock().unwrap().remove(&key);
+            return;
+        }
+        MARQUEE.lock().unwrap().insert(
+            key,
+            MarqueeState {
+                counter: 0,
+                delay: 2,
+                last_line: 0,
+            },
+        );
+        let mut this = self.clone();
+        crate::app::add_timeout(0.333, move || this.marquee_tick());
+    }
+
+    fn marquee_tick(&mut self) {
+        if self.was_deleted() {
+            return;
+        }
+        let key = self._inner as usize;
+        let mut map = MARQUEE.lock().unwrap();
+        let state = match map.get_mut(&key) {
+            Some(s) => s,
+            None => return,
+        };
+        let line = self.value();
+        if line != state.last_line {
+            state.counter = 0;
+            state.last_line = line;
+        }
+        if line > 0 {
+            if let Some(text) = self.text(line) {
+                let text_w = crate::draw::width(&text) as i32;
+                if text_w > self.width() {
+                    let overflow = text_w - self.width();
+                    let pos = (state.counter - state.delay).max(0);
+                    let offset = pos.min(overflow);
+                    self.set_hposition(offset as u32);
+                    state.counter += 1;
+                    if offset >= overflow {
+                        state.counter = 0;
+                    }
+                } else {
+                    state.counter = 0;
+                    self.set_hposition(0);
+                }
+            }
+        }
+        drop(map);
+        self.redraw();
+        let mut this = self.clone();
+        crate::app::repeat_timeout(0.333, move || this.marquee_tick());
+    }
+}
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A message streamed from an async directory walk, to be consumed in the main
+/// `app::wait` loop and appended to a [`FileBrowser`].
+#[derive(Clone, Debug)]
+pub enum DirMessage {
+    /// A batch of entries produced by the worker thread
+    Batch(Vec<String>),
+    /// The walk finished
+    Done,
+}
+
+/// Handle for an in-flight async directory load, used to cancel it
+#[derive(Clone, Debug)]
+pub struct DirLoad {
+    cancel: Arc<AtomicBool>,
+}
+
+impl DirLoad {
+    /// Aborts the in-flight walk; the worker stops before its next entry and
+    /// sends no further batch and no `Done`
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+}
+
+impl FileBrowser {
+    /// Walks `dir` on a worker thread, applying the current glob filter and
+    /// [`FileType`], and streams results back to the main loop through `sender`
+    /// in batches. The caller drains the matching receiver inside `app::wait`
+    /// and appends rows. Returns a [`DirLoad`] whose `cancel()` aborts the walk,
+    /// so switching directories stops the previous one.
+    pub fn load_async(&mut self, dir: &std::path::Path, sender: crate::app::Sender<DirMessage>) -> DirLoad {
+        assert!(!self.was_deleted());
+        let cancel = Arc::new(AtomicBool::new(false));
+        let token = cancel.clone();
+        let dir = dir.to_path_buf();
+        let pattern = self.filter();
+        let filetype = self.filetype();
+        std::thread::spawn(move || {
+            let mut batch = Vec::with_capacity(64);
+            if let Ok(read) = std::fs::read_dir(&dir) {
+                for entry in read.flatten() {
+                    if token.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    let is_dir = entry.path().is_dir();
+                    match filetype {
+                        FileType::Dirs if !is_dir => continue,
+                        _ => {}
+                    }
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if let Some(ref pat) = pattern {
+                        if !glob_match(pat, &name) {
+                            continue;
+                        }
+                    }
+                    batch.push(name);
+                    if batch.len() >= 64 {
+                        sender.send(DirMessage::Batch(std::mem::take(&mut batch)));
+                    }
+                }
+            }
+            if !token.load(Ordering::SeqCst) {
+                if !batch.is_empty() {
+                    sender.send(DirMessage::Batch(batch));
+                }
+                sender.send(DirMessage::Done);
+            }
+        });
+        DirLoad { cancel }
+    }
+}
+
+/// Minimal `*`/`?` glob match used to filter directory entries off-thread
+fn glob_match(pattern: &str, name: &str) -> bool {
+    fn inner(p: &[u8], s: &[u8]) -> bool {
+        match p.first() {
+            None => s.is_empty(),
+            Some(b'*') => inner(&p[1..], s) || (!s.is_empty() && inner(p, &s[1..])),
+            Some(b'?') => !s.is_empty() && inner(&p[1..], &s[1..]),
+            Some(&c) => !s.is_empty() && s[0] == c && inner(&p[1..], &s[1..]),
+        }
+    }
+    inner(pattern.as_bytes(), name.as_bytes())
+}
+
+// Keeps CheckBrowser column-width arrays alive for the lifetime of the widget,
+// since FLTK stores the pointer rather than copying.
+static CHECK_COLS: Mutex<BTreeMap<usize, Vec<i32>>> = Mutex::new(BTreeMap::new());
+
+impl CheckBrowser {
+    /// Sets the icon shown next to the checkbox at `line`
+    pub fn set_icon<I: ImageExt>(&mut self, line: i32, image: Option<I>) {
+        assert!(!self.was_deleted());
+        if let Some(mut image) = image {
+            assert!(!image.was_deleted());
+            unsafe {
+                image.increment_arc();
+                Fl_Check_Browser_set_icon(self._inner, line, image.as_image_ptr() as *mut _)
+            }
+        } else {
+            unsafe { Fl_Check_Browser_set_icon(self._inner, line, std::ptr::null_mut()) }
+        }
+    }
+
+    /// Returns the icon at `line`, if any
+    pub fn icon(&self, line: i32) -> Option<Box<dyn ImageExt>> {
+        assert!(!self.was_deleted());
+        unsafe {
+            let ptr = Fl_Check_Browser_icon(self._inner, line);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Box::new(Image::from_image_ptr(
+                    ptr as *mut fltk_sys::image::Fl_Image,
+                )))
+            }
+        }
+    }
+
+    /// Removes the icon at `line`
+    pub fn remove_icon(&mut self, line: i32) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Check_Browser_remove_icon(self._inner, line) }
+    }
+
+    /// Sets the column widths (in pixels) for tab-separated check items. A
+    /// trailing zero terminates the array as FLTK expects.
+    pub fn set_column_widths(&mut self, widths: &[i32]) {
+        assert!(!self.was_deleted());
+        let mut widths = widths.to_vec();
+        widths.push(0);
+        let ptr = widths.as_ptr();
+        CHECK_COLS.lock().unwrap().insert(self._inner as usize, widths);
+        unsafe { Fl_Check_Browser_set_column_widths(self._inner, ptr) }
+    }
+
+    /// Sets the column separator character (e.g. `\t`)
+    pub fn set_column_char(&mut self, c: char) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Check_Browser_set_column_char(self._inner, c as raw::c_char) }
+    }
+}
+
+/// Sort direction for click-to-sort columns
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SortDirection {
+    /// Ascending order
+    Ascending,
+    /// Descending order
+    Descending,
+}
+
+struct SortState {
+    enabled: bool,
+    column: i32,
+    dir: SortDirection,
+    sep: char,
+}
+
+static SORT: Mutex<BTreeMap<usize, SortState>> = Mutex::new(BTreeMap::new());
+
+impl Browser {
+    /// Enables spreadsheet-style click-to-sort columns. Cells are split on the
+    /// tab character.
+    pub fn set_sortable(&mut self, sortable: bool) {
+        assert!(!self.was_deleted());
+        let key = self._inner as usize;
+        if !sortable {
+            SORT.lock().unwrap().remove(&key);
+            return;
+        }
+        SORT.lock().unwrap().insert(
+            key,
+            SortState {
+                enabled: true,
+                column: 0,
+                dir: SortDirection::Ascending,
+                sep: '\t',
+            },
+        );
+    }
+
+    /// Re-sorts the rows by the text in `col`, using a numeric comparison when
+    /// every cell parses as a number and a lexical one otherwise.
+    pub fn sort_by_column(&mut self, col: i32, dir: SortDirection) {
+        assert!(!self.was_deleted());
+        let sep = SORT
+            .lock()
+            .unwrap()
+            .get(&(self._inner as usize))
+            .map(|s| s.sep)
+            .unwrap_or('\t');
+        let n = self.size();
+        let mut rows: Vec<String> = (1..=n).filter_map(|i| self.text(i)).collect();
+        let cell = |row: &str| -> String {
+            row.split(sep)
+                .nth(col as usize)
+                .unwrap_or("")
+                .to_string()
+        };
+        let all_numeric = rows
+            .iter()
+            .all(|r| cell(r).trim().parse::<f64>().is_ok());
+        rows.sort_by(|a, b| {
+            let (ca, cb) = (cell(a), cell(b));
+            let ord = if all_numeric {
+                ca.trim()
+                    .parse::<f64>()
+                    .unwrap_or(0.0)
+                    .partial_cmp(&cb.trim().parse::<f64>().unwrap_or(0.0))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            } else {
+                ca.cmp(&cb)
+            };
+            if dir == SortDirection::Descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+        self.clear();
+        for row in rows {
+            self.add(&row);
+        }
+        if let Some(state) = SORT.lock().unwrap().get_mut(&(self._inner as usize)) {
+            state.column = col;
+            state.dir = dir;
+        }
+    }
+
+    /// Handles a header click on `col`, toggling direction on repeated clicks
+    /// and re-sorting. Call from the widget's event handler.
+    pub fn handle_header_click(&mut self, col: i32) {
+        let (enabled, prev_col, prev_dir) = {
+            let map = SORT.lock().unwrap();
+            match map.get(&(self._inner as usize)) {
+                Some(s) => (s.enabled, s.column, s.dir),
+                None => (false, 0, SortDirection::Ascending),
+            }
+        };
+        if !enabled {
+            return;
+        }
+        let dir = if col == prev_col && prev_dir == SortDirection::Ascending {
+            SortDirection::Descending
+        } else {
+            SortDirection::Ascending
+        };
+        self.sort_by_column(col, dir);
+    }
+}
+
+impl CheckBrowser {
+    /// Returns the 1-based indices of all currently-checked items in one pass
+    pub fn checked_items(&self) -> Vec<i32> {
+        assert!(!self.was_deleted());
+        let n = self.nitems() as i32;
+        (1..=n).filter(|&i| self.checked(i)).collect()
+    }
+
+    /// Returns the text of all currently-checked items
+    pub fn checked_texts(&self) -> Vec<String> {
+        assert!(!self.was_deleted());
+        self.checked_items()
+            .into_iter()
+            .filter_map(|i| self.text(i))
+            .collect()
+    }
+
+    /// Restores a saved selection, checking exactly the given indices
+    pub fn set_checked_items(&mut self, items: &[i32]) {
+        assert!(!self.was_deleted());
+        self.check_none();
+        for &i in items {
+            self.set_checked(i);
+        }
+    }
+}
+
+#[cfg(test)]
+mod glob_tests {
+    use super::glob_match;
+
+    #[test]
+    fn literals_and_wildcards() {
+        assert!(glob_match("readme.txt", "readme.txt"));
+        assert!(!glob_match("readme.txt", "readme.md"));
+        assert!(glob_match("*.txt", "notes.txt"));
+        assert!(!glob_match("*.txt", "notes.md"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn question_mark_matches_single_char() {
+        assert!(glob_match("?.rs", "a.rs"));
+        assert!(!glob_match("?.rs", "ab.rs"));
+        assert!(!glob_match("?.rs", ".rs"));
+    }
+
+    #[test]
+    fn star_spans_any_run() {
+        assert!(glob_match("a*c", "ac"));
+        assert!(glob_match("a*c", "abbbc"));
+        assert!(!glob_match("a*c", "abbb"));
+    }
+}
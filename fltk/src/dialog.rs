@@ -10,6 +10,14 @@ use std::{
 #[derive(Debug)]
 pub struct FileDialog {
     _inner: *mut Fl_Native_File_Chooser,
+    // Cached request parameters and results for the optional XDG portal backend.
+    #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+    title: String,
+    filter: String,
+    #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+    dtype: FileDialogType,
+    #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+    portal_files: Vec<std::path::PathBuf>,
 }
 
 /// Re-alias FileDialog to NativeFileChooser (Fl_Native_File_Chooser)
@@ -62,6 +70,102 @@ impl std::ops::BitOr<FileDialogOptions> for FileDialogOptions {
     }
 }
 
+/// A typed, composable file filter, mirroring GTK's `FileFilter` name/patterns
+/// model. Lists of these serialize to FLTK's tab/newline filter encoding
+/// (e.g. `"C++ Files\t*.{cxx,H}\nTxt Files\t*.txt"`) via
+/// [`set_filters`](struct.FileDialog.html#method.set_filters).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileFilter {
+    name: String,
+    patterns: Vec<String>,
+}
+
+impl FileFilter {
+    /// Creates a new, empty filter with the given descriptive name
+    pub fn new(name: &str) -> FileFilter {
+        FileFilter {
+            name: name.to_string(),
+            patterns: vec![],
+        }
+    }
+
+    /// Adds a single wildcard pattern (e.g. `"*.rs"`)
+    pub fn add_pattern(&mut self, pattern: &str) -> &mut Self {
+        self.patterns.push(pattern.to_string());
+        self
+    }
+
+    /// Adds several wildcard patterns at once
+    pub fn add_patterns<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, patterns: I) -> &mut Self {
+        for p in patterns {
+            self.patterns.push(p.as_ref().to_string());
+        }
+        self
+    }
+
+    /// The filter's descriptive name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The filter's wildcard patterns
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Collapses the patterns into a single FLTK wildcard. Patterns sharing the
+    /// `*.` prefix fold into `*.{ext,ext}`; anything else is passed through.
+    fn to_wildcard(&self) -> String {
+        let exts: Option<Vec<&str>> = self
+            .patterns
+            .iter()
+            .map(|p| p.strip_prefix("*."))
+            .collect();
+        match exts {
+            Some(exts) if exts.len() > 1 => format!("*.{{{}}}", exts.join(",")),
+            _ => self.patterns.join(" "),
+        }
+    }
+}
+
+/// Serializes a filter list into FLTK's `\t`/`\n` encoding.
+fn serialize_filters(filters: &[FileFilter]) -> String {
+    filters
+        .iter()
+        .map(|f| format!("{}\t{}", f.name, f.to_wildcard()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses FLTK's `\t`/`\n` filter encoding back into a list of filters,
+/// expanding `*.{a,b}` groups into individual patterns.
+fn parse_filters(encoded: &str) -> Vec<FileFilter> {
+    encoded
+        .split('\n')
+        .filter(|g| !g.is_empty())
+        .map(|group| {
+            let (name, wildcard) = group.split_once('\t').unwrap_or((group, group));
+            let mut patterns = vec![];
+            if let Some(inner) = wildcard
+                .strip_prefix("*.{")
+                .and_then(|s| s.strip_suffix('}'))
+            {
+                for ext in inner.split(',') {
+                    patterns.push(format!("*.{}", ext));
+                }
+            } else {
+                for w in wildcard.split(&[' ', ';'][..]).filter(|w| !w.is_empty()) {
+                    patterns.push(w.to_string());
+                }
+            }
+            FileFilter {
+                name: name.to_string(),
+                patterns,
+            }
+        })
+        .collect()
+}
+
 impl FileDialog {
     /// Creates an new file dialog
     pub fn new(op: FileDialogType) -> FileDialog {
@@ -70,6 +174,13 @@ impl FileDialog {
             assert!(!file_dialog.is_null());
             FileDialog {
                 _inner: file_dialog,
+                #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+                title: String::new(),
+                filter: String::new(),
+                #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+                dtype: op,
+                #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+                portal_files: vec![],
             }
         }
     }
@@ -77,6 +188,10 @@ impl FileDialog {
     /// Returns the chosen file name
     pub fn filename(&self) -> std::path::PathBuf {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+        if let Some(first) = self.portal_files.first() {
+            return first.clone();
+        }
         unsafe {
             let cnt = Fl_Native_File_Chooser_count(self._inner);
             if cnt == 0 {
@@ -94,6 +209,10 @@ impl FileDialog {
     /// Returns the chosen file names
     pub fn filenames(&self) -> Vec<std::path::PathBuf> {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+        if !self.portal_files.is_empty() {
+            return self.portal_files.clone();
+        }
         unsafe {
             let cnt = Fl_Native_File_Chooser_count(self._inner);
             let mut names: Vec<std::path::PathBuf> = vec![];
@@ -145,9 +264,23 @@ impl FileDialog {
         Ok(())
     }
 
-    /// Shows the file dialog
+    /// Shows the file dialog.
+    /// When built with the `portal` feature on Linux/FreeBSD this drives the
+    /// `org.freedesktop.portal.FileChooser` DBus interface (working inside
+    /// Flatpak/Snap and on Wayland); otherwise it calls through to FLTK's
+    /// native chooser. The call stays blocking: the portal round-trip is run
+    /// to completion on a worker thread before returning. When the portal
+    /// returns no selection (cancelled or unavailable), FLTK's native chooser
+    /// is shown as well.
     pub fn show(&mut self) {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+        {
+            self.portal_files = portal::run(self.dtype, &self.title, &self.filter);
+            if !self.portal_files.is_empty() {
+                return;
+            }
+        }
         unsafe {
             Fl_Native_File_Chooser_show(self._inner);
         }
@@ -162,12 +295,20 @@ impl FileDialog {
     /// Sets the type for the dialog
     pub fn set_type(&mut self, op: FileDialogType) {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+        {
+            self.dtype = op;
+        }
         unsafe { Fl_Native_File_Chooser_set_type(self._inner, op as i32) }
     }
 
     /// Sets the title for the dialog
     pub fn set_title(&mut self, title: &str) {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "portal", any(target_os = "linux", target_os = "freebsd")))]
+        {
+            self.title = title.to_string();
+        }
         let title = CString::safe_new(title);
         unsafe { Fl_Native_File_Chooser_set_title(self._inner, title.as_ptr()) }
     }
@@ -180,10 +321,22 @@ impl FileDialog {
     /// A list of descriptive names and wildcards (eg. `"C++ Files\t*.{cxx,H}\nTxt Files\t*.txt"`)
     pub fn set_filter(&mut self, f: &str) {
         assert!(!self._inner.is_null());
+        self.filter = f.to_string();
         let f = CString::safe_new(f);
         unsafe { Fl_Native_File_Chooser_set_filter(self._inner, f.as_ptr()) }
     }
 
+    /// Sets the dialog filter from a typed [`FileFilter`] list, serializing it
+    /// into FLTK's tab/newline encoding internally
+    pub fn set_filters(&mut self, filters: &[FileFilter]) {
+        self.set_filter(&serialize_filters(filters));
+    }
+
+    /// Parses the current filter back into a [`FileFilter`] list
+    pub fn filters(&self) -> Vec<FileFilter> {
+        parse_filters(&self.filter)
+    }
+
     /// Sets the preset filter for the dialog
     pub fn set_preset_file(&mut self, f: &str) {
         assert!(!self._inner.is_null());
@@ -218,6 +371,211 @@ impl Drop for FileDialog {
     }
 }
 
+/// XDG Desktop Portal backend for [`FileDialog`], used instead of FLTK's GTK
+/// chooser when the `portal` feature is enabled. The call is issued over the
+/// session bus with `gdbus`, so it needs no extra runtime dependency and works
+/// inside Flatpak/Snap sandboxes where GTK file access is blocked.
+#[cfg(all(
+    any(feature = "portal", feature = "use-portal"),
+    any(target_os = "linux", target_os = "freebsd")
+))]
+mod portal {
+    use super::FileDialogType;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    /// Runs the portal request on a worker thread and blocks for its response,
+    /// returning the selected paths (empty if the user cancelled or the portal
+    /// is unavailable, in which case the caller falls back to FLTK).
+    pub fn run(dtype: FileDialogType, title: &str, filter: &str) -> Vec<PathBuf> {
+        let save = matches!(
+            dtype,
+            FileDialogType::BrowseSaveFile | FileDialogType::BrowseSaveDir
+        );
+        let multi = matches!(
+            dtype,
+            FileDialogType::BrowseMultiFile | FileDialogType::BrowseMultiDir
+        );
+        let directory = matches!(
+            dtype,
+            FileDialogType::BrowseDir | FileDialogType::BrowseMultiDir | FileDialogType::BrowseSaveDir
+        );
+        let method = if save { "SaveFile" } else { "OpenFile" };
+        let options = build_options(multi, directory, filter);
+        let title = title.to_string();
+        let handle = std::thread::spawn(move || call(method, &title, &options));
+        handle.join().unwrap_or_default()
+    }
+
+    /// Serializes the a{sv} options dictionary portion of the request. FLTK's
+    /// `\t`/`\n` filter encoding is translated into the portal's
+    /// `(name, [(0, glob)])` filter tuples.
+    fn build_options(multi: bool, directory: bool, filter: &str) -> String {
+        let mut filters = String::new();
+        for group in filter.split('\n').filter(|g| !g.is_empty()) {
+            let (name, globs) = match group.split_once('\t') {
+                Some((n, g)) => (n, g),
+                None => (group, group),
+            };
+            let mut patterns = String::new();
+            for glob in globs.split(&[' ', ';'][..]).filter(|g| !g.is_empty()) {
+                patterns.push_str(&format!("(uint32 0, '{}'),", glob));
+            }
+            filters.push_str(&format!("('{}', [{}]),", name, patterns.trim_end_matches(',')));
+        }
+        let filters = filters.trim_end_matches(',');
+        format!(
+            "{{'multiple': <{}>, 'directory': <{}>, 'filters': <[{}]>}}",
+            multi, directory, filters
+        )
+    }
+
+    /// Invokes the portal method and parses `file://` URIs out of the response.
+    fn call(method: &str, title: &str, options: &str) -> Vec<PathBuf> {
+        let out = Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.portal.Desktop",
+                "--object-path",
+                "/org/freedesktop/portal/desktop",
+                "--method",
+                &format!("org.freedesktop.portal.FileChooser.{}", method),
+                "",
+                title,
+                options,
+            ])
+            .output();
+        let out = match out {
+            Ok(o) if o.status.success() => o,
+            _ => return vec![],
+        };
+        let handle = String::from_utf8_lossy(&out.stdout);
+        wait_response(handle.trim().trim_matches(|c| c == '(' || c == ')' || c == '\'' || c == ','))
+    }
+
+    /// Monitors the returned request object path for its
+    /// `org.freedesktop.portal.Request.Response` signal and collects the `uris`
+    /// array once the user has confirmed the selection. `gdbus monitor`
+    /// subscribes to the bus and prints signal emissions as they arrive; we read
+    /// its output until the `Response` signal appears, then stop the monitor.
+    fn wait_response(request_path: &str) -> Vec<PathBuf> {
+        if request_path.is_empty() {
+            return vec![];
+        }
+        use std::io::{BufRead, BufReader};
+        let mut child = match Command::new("gdbus")
+            .args([
+                "monitor",
+                "--session",
+                "--dest",
+                "org.freedesktop.portal.Desktop",
+                "--object-path",
+                request_path,
+            ])
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+        {
+            Ok(c) => c,
+            Err(_) => return vec![],
+        };
+        let mut result = vec![];
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if let Some(rest) = line.split_once(".Response").map(|(_, r)| r) {
+                    // The signal body is `(uint32 <response>, {'uris': <[...]>})`;
+                    // a non-zero response code means the user cancelled.
+                    if response_code(rest) == 0 {
+                        result = parse_uris(rest);
+                    }
+                    break;
+                }
+            }
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+        result
+    }
+
+    /// Extracts the leading `uint32 <n>` response code from a `Response` signal
+    /// body, defaulting to a non-zero (cancelled) code when it can't be parsed.
+    fn response_code(body: &str) -> u32 {
+        body.split_once("uint32")
+            .and_then(|(_, r)| r.trim_start().split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(1)
+    }
+
+    /// Pulls the `file://` URIs out of a portal response body.
+    fn parse_uris(body: &str) -> Vec<PathBuf> {
+        body.split('\'')
+            .filter(|s| s.starts_with("file://"))
+            .filter_map(|uri| uri.strip_prefix("file://"))
+            .map(|p| PathBuf::from(percent_decode(p)))
+            .collect()
+    }
+
+    /// Minimal percent-decoding for the path component of a `file://` URI.
+    fn percent_decode(s: &str) -> String {
+        let bytes = s.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' && i + 2 < bytes.len() {
+                if let Ok(b) = u8::from_str_radix(&s[i + 1..i + 3], 16) {
+                    out.push(b);
+                    i += 3;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+}
+
+/// Overrides the text of the generic `OK`/`Cancel` buttons used by the free
+/// `message`/`alert`/`input` helpers, wiring to FLTK's `fl_ok`/`fl_cancel`
+/// globals. Passing `None` leaves that button at its current label. The strings
+/// are leaked for FLTK's process-lifetime `const char*` globals, matching how
+/// the `FileChooser` label setters already behave.
+fn apply_button_labels(ok: Option<&str>, cancel: Option<&str>) {
+    unsafe {
+        if let Some(ok) = ok {
+            Fl_message_set_ok_label(CString::safe_new(ok).into_raw());
+        }
+        if let Some(cancel) = cancel {
+            Fl_message_set_cancel_label(CString::safe_new(cancel).into_raw());
+        }
+    }
+}
+
+/// Sets the label of the generic dialogs' `OK` button for subsequent calls
+pub fn set_ok_label(label: &str) {
+    apply_button_labels(Some(label), None);
+}
+
+/// Sets the label of the generic dialogs' `Cancel` button for subsequent calls
+pub fn set_cancel_label(label: &str) {
+    apply_button_labels(None, Some(label));
+}
+
+/// Like [`input`], but with caller-supplied `OK`/`Cancel` button labels, for
+/// localized UIs or `"Save"`/`"Open"`-style wording.
+pub fn input_with_labels(
+    x: i32,
+    y: i32,
+    txt: &str,
+    deflt: &str,
+    ok: &str,
+    cancel: &str,
+) -> Option<String> {
+    apply_button_labels(Some(ok), Some(cancel));
+    input(x, y, txt, deflt)
+}
+
 /// Displays a message box
 pub fn message(x: i32, y: i32, txt: &str) {
     unsafe {
@@ -547,6 +905,145 @@ bitflags! {
     }
 }
 
+#[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+static PORTAL_SEL: Mutex<BTreeMap<usize, Vec<std::path::PathBuf>>> = Mutex::new(BTreeMap::new());
+
+/// Returns `true` when running inside a Flatpak/Snap-style sandbox where the
+/// portal backend should be preferred.
+#[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+fn is_sandboxed() -> bool {
+    std::path::Path::new("/.flatpak-info").exists() || std::env::var_os("GTK_USE_PORTAL").is_some()
+}
+
+bitflags! {
+    /// Listing options for a [`FileChooser`] and for [`list_dir`].
+    pub struct ExplorerOptions: i32 {
+        /// List entries whose name begins with a dot
+        const ShowHidden = 1;
+        /// Sort directories ahead of regular files
+        const DirsFirst = 2;
+        /// Sort case-insensitively
+        const CaseInsensitive = 4;
+    }
+}
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+static EXPLORER_OPTS: Mutex<BTreeMap<usize, ExplorerOptions>> = Mutex::new(BTreeMap::new());
+
+/// Per-chooser favorites, mapping the chooser pointer to its preferences group
+/// name and the ordered list of pinned directories, persisted to a file of its
+/// own (see `favorites_path`). This list is separate from FLTK's own favorites
+/// preferences: it is not read from them or written to them, and the
+/// chooser's favorites menu does not show it.
+static FAVORITES: Mutex<BTreeMap<usize, (String, Vec<String>)>> = Mutex::new(BTreeMap::new());
+
+/// Path of the preferences file holding the favorites for `group`.
+fn favorites_path(group: &str) -> std::path::PathBuf {
+    let mut base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| std::path::PathBuf::from(h).join(".config")))
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    base.push("fltk");
+    base.push(format!("{}.favorites", group));
+    base
+}
+
+/// Reads the persisted favorites for `group`, one directory per line.
+fn load_favorites(group: &str) -> Vec<String> {
+    match std::fs::read_to_string(favorites_path(group)) {
+        Ok(s) => s.lines().filter(|l| !l.is_empty()).map(|l| l.to_string()).collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Writes `favs` back to the preferences file for `group`, one per line.
+fn save_favorites(group: &str, favs: &[String]) {
+    let path = favorites_path(group);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, favs.join("\n"));
+}
+
+/// Returns `true` if `name` matches any of the filter's wildcard patterns
+/// (an empty filter matches everything). Supports `*` and `?`.
+fn filter_matches(name: &str, filter: &FileFilter) -> bool {
+    if filter.patterns().is_empty() {
+        return true;
+    }
+    filter.patterns().iter().any(|p| wildcard_match(p, name))
+}
+
+/// Glob match of a single `*`/`?` wildcard against a file name.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let (p, n): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut pi, mut ni) = (0, 0);
+    let (mut star, mut mark) = (None, 0);
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some(pi);
+            mark = ni;
+            pi += 1;
+        } else if let Some(s) = star {
+            pi = s + 1;
+            mark += 1;
+            ni = mark;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+/// Walks `path`, keeping directories and the files that match `filter`,
+/// optionally dropping dotfiles (unless [`ExplorerOptions::ShowHidden`]), and sorting with
+/// directories first and/or case-insensitively per `opts`. A pure-Rust helper
+/// for custom file-browser widgets that avoids per-app `std::fs` boilerplate.
+pub fn list_dir(
+    path: &std::path::Path,
+    filter: &FileFilter,
+    opts: ExplorerOptions,
+) -> Vec<std::path::PathBuf> {
+    let mut entries: Vec<std::path::PathBuf> = match std::fs::read_dir(path) {
+        Ok(rd) => rd.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+        Err(_) => return vec![],
+    };
+    entries.retain(|p| {
+        let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if !opts.contains(ExplorerOptions::ShowHidden) && name.starts_with('.') {
+            return false;
+        }
+        p.is_dir() || filter_matches(name, filter)
+    });
+    entries.sort_by(|a, b| {
+        if opts.contains(ExplorerOptions::DirsFirst) {
+            match (a.is_dir(), b.is_dir()) {
+                (true, false) => return std::cmp::Ordering::Less,
+                (false, true) => return std::cmp::Ordering::Greater,
+                _ => {}
+            }
+        }
+        let (an, bn) = (
+            a.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+            b.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+        );
+        if opts.contains(ExplorerOptions::CaseInsensitive) {
+            an.to_lowercase().cmp(&bn.to_lowercase())
+        } else {
+            an.cmp(&bn)
+        }
+    });
+    entries
+}
+
 impl FileChooser {
     /// Instantiates a new FileChooser
     pub fn new(dir: &str, pattern: &str, typ: FileChooserType, title: &str) -> FileChooser {
@@ -670,6 +1167,10 @@ impl FileChooser {
     /// Gets the count of chosen items
     pub fn count(&mut self) -> u32 {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+        if let Some(sel) = PORTAL_SEL.lock().unwrap().get(&(self._inner as usize)) {
+            return sel.len() as u32;
+        }
         unsafe { Fl_File_Chooser_count(self._inner) as u32 }
     }
 
@@ -709,6 +1210,20 @@ impl FileChooser {
         unsafe { Fl_File_Chooser_set_filter(self._inner, pattern.as_ptr()) }
     }
 
+    /// Sets the filter from a typed [`FileFilter`] list, serializing it into
+    /// FLTK's tab/newline encoding internally
+    pub fn set_filters(&mut self, filters: &[FileFilter]) {
+        self.set_filter(&serialize_filters(filters));
+    }
+
+    /// Parses the current filter back into a [`FileFilter`] list
+    pub fn filters(&mut self) -> Vec<FileFilter> {
+        match self.filter() {
+            Some(f) => parse_filters(&f),
+            None => vec![],
+        }
+    }
+
     /// Gets the filter of the FileChooser
     pub fn filter(&mut self) -> Option<String> {
         assert!(!self._inner.is_null());
@@ -732,6 +1247,13 @@ impl FileChooser {
         unsafe { Fl_File_Chooser_filter_value(self._inner) as u32 }
     }
 
+    /// Returns the raw wildcard pattern of the currently selected filter group,
+    /// for callers that post-filter results themselves.
+    pub fn current_filter_pattern(&mut self) -> Option<String> {
+        let idx = self.filter_value() as usize;
+        self.filters().get(idx).map(|f| f.to_wildcard())
+    }
+
     /// Sets the filter for the dialog, can be:
     /// A single wildcard (eg. `"*.txt"`)
     /// Multiple wildcards (eg. `"*.{cxx,h,H}"`)
@@ -805,6 +1327,86 @@ impl FileChooser {
         }
     }
 
+    /// Sets the label of the Cancel button. FLTK's `Fl_File_Chooser` draws its
+    /// cancel button from the shared `fl_cancel` global, so this affects later
+    /// generic dialogs too.
+    pub fn set_cancel_label(&mut self, l: &str) {
+        assert!(!self._inner.is_null());
+        apply_button_labels(None, Some(l));
+    }
+
+    /// Selects the preferences group under which this chooser's favorites are
+    /// stored, so separate apps or profiles can keep distinct sets. Loads any
+    /// persisted entries for that group immediately.
+    pub fn set_favorites_prefs_group(&mut self, group: &str) {
+        let favs = load_favorites(group);
+        FAVORITES
+            .lock()
+            .unwrap()
+            .insert(self._inner as usize, (group.to_string(), favs));
+    }
+
+    /// Returns the current favorite directories
+    pub fn favorites(&mut self) -> Vec<String> {
+        FAVORITES
+            .lock()
+            .unwrap()
+            .get(&(self._inner as usize))
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default()
+    }
+
+    /// Adds a directory to the favorites list and persists it
+    pub fn add_favorite(&mut self, dir: &str) {
+        let mut map = FAVORITES.lock().unwrap();
+        let entry = map
+            .entry(self._inner as usize)
+            .or_insert_with(|| ("favorites".to_string(), vec![]));
+        if !entry.1.iter().any(|d| d == dir) {
+            entry.1.push(dir.to_string());
+            save_favorites(&entry.0, &entry.1);
+        }
+    }
+
+    /// Removes a directory from the favorites list and persists the change
+    pub fn remove_favorite(&mut self, dir: &str) {
+        if let Some(entry) = FAVORITES.lock().unwrap().get_mut(&(self._inner as usize)) {
+            entry.1.retain(|d| d != dir);
+            save_favorites(&entry.0, &entry.1);
+        }
+    }
+
+    /// Clears the favorites list and persists the empty set
+    pub fn clear_favorites(&mut self) {
+        if let Some(entry) = FAVORITES.lock().unwrap().get_mut(&(self._inner as usize)) {
+            entry.1.clear();
+            save_favorites(&entry.0, &entry.1);
+        }
+    }
+
+    /// Stores the listing options, returned by [`options`](Self::options).
+    /// Only [`ExplorerOptions::ShowHidden`] affects the chooser itself: it is
+    /// reflected onto the "show hidden" toggle button before a rescan. The
+    /// sort flags do not change the chooser's own listing order.
+    pub fn set_options(&mut self, opts: ExplorerOptions) {
+        assert!(!self._inner.is_null());
+        EXPLORER_OPTS.lock().unwrap().insert(self._inner as usize, opts);
+        if let Some(mut btn) = self.show_hidden_button() {
+            btn.set_value(opts.contains(ExplorerOptions::ShowHidden));
+        }
+        self.rescan();
+    }
+
+    /// Returns the current listing options (defaults to empty)
+    pub fn options(&self) -> ExplorerOptions {
+        EXPLORER_OPTS
+            .lock()
+            .unwrap()
+            .get(&(self._inner as usize))
+            .copied()
+            .unwrap_or_else(ExplorerOptions::empty)
+    }
+
     /// Add preview to the FileChooser
     pub fn set_preview(&mut self, e: bool) {
         assert!(!self._inner.is_null());
@@ -829,9 +1431,33 @@ impl FileChooser {
         unsafe { Fl_File_Chooser_rescan_keep_filename(self._inner) }
     }
 
-    /// Shows the File Chooser
+    /// Shows the File Chooser.
+    /// With the `use-portal` feature on Linux/FreeBSD, and when a sandbox is
+    /// detected (`/.flatpak-info` or `GTK_USE_PORTAL`), the selection is routed
+    /// through the `org.freedesktop.portal.FileChooser` DBus interface instead
+    /// of FLTK's in-process chooser, which cannot see the real filesystem there.
     pub fn show(&mut self) {
         assert!(!self._inner.is_null());
+        #[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+        if is_sandboxed() {
+            let typ = self.get_type();
+            let directory = typ.contains(FileChooserType::Directory);
+            let multi = typ.contains(FileChooserType::Multi);
+            let dtype = match (directory, multi) {
+                (true, true) => FileDialogType::BrowseMultiDir,
+                (true, false) => FileDialogType::BrowseDir,
+                (false, true) => FileDialogType::BrowseMultiFile,
+                (false, false) => FileDialogType::BrowseFile,
+            };
+            let files = portal::run(dtype, &self.label(), &self.filter().unwrap_or_default());
+            // Only take over when the portal actually returned a selection;
+            // otherwise (cancelled or portal unavailable) fall back to FLTK.
+            if !files.is_empty() {
+                PORTAL_SEL.lock().unwrap().insert(self._inner as usize, files);
+                return;
+            }
+            PORTAL_SEL.lock().unwrap().remove(&(self._inner as usize));
+        }
         unsafe { Fl_File_Chooser_show(self._inner) }
     }
 
@@ -911,6 +1537,12 @@ impl FileChooser {
         if f == 0 {
             f = 1;
         }
+        #[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+        if let Some(sel) = PORTAL_SEL.lock().unwrap().get(&(self._inner as usize)) {
+            return sel
+                .get((f - 1) as usize)
+                .map(|p| p.to_string_lossy().into_owned());
+        }
         unsafe {
             let ptr = Fl_File_Chooser_value(self._inner, f as i32);
             if ptr.is_null() {
@@ -932,6 +1564,22 @@ impl FileChooser {
         unsafe { Fl_File_Chooser_set_value(self._inner, filename.as_ptr()) }
     }
 
+    /// Returns all selected entries as [`PathBuf`]s, iterating index 1 through
+    /// [`count`](Self::count). Returns an empty vector when nothing was chosen,
+    /// the way the native chooser's `filenames()` does.
+    pub fn selected_files(&mut self) -> Vec<std::path::PathBuf> {
+        let n = self.count();
+        (1..=n)
+            .filter_map(|i| self.value(i))
+            .map(std::path::PathBuf::from)
+            .collect()
+    }
+
+    /// Convenience iterator over [`selected_files`](Self::selected_files).
+    pub fn values(&mut self) -> impl Iterator<Item = std::path::PathBuf> {
+        self.selected_files().into_iter()
+    }
+
     /// Returns whether the FileChooser is visible or not
     pub fn visible(&mut self) -> bool {
         assert!(!self._inner.is_null());
@@ -1045,8 +1693,251 @@ impl Drop for FileChooser {
     }
 }
 
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+struct SharedResult {
+    result: Option<Vec<std::path::PathBuf>>,
+    waker: Option<Waker>,
+}
+
+/// The future returned by [`FileChooser::show_async`]/[`FileDialog::show_async`].
+/// It resolves with the selected paths once the dialog is dismissed; the poll
+/// loop is driven by an `app::add_timeout` tick integrated with the main loop.
+pub struct DialogFuture {
+    shared: Rc<RefCell<SharedResult>>,
+}
+
+impl Future for DialogFuture {
+    type Output = Vec<std::path::PathBuf>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut s = self.shared.borrow_mut();
+        if let Some(r) = s.result.take() {
+            Poll::Ready(r)
+        } else {
+            s.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Polls a shown `FileChooser` (by raw pointer, without taking ownership) until
+/// it is dismissed, then fills `shared` and wakes the future.
+fn poll_chooser(inner: usize, shared: Rc<RefCell<SharedResult>>) {
+    crate::app::add_timeout(0.05, move || {
+        let mut chooser =
+            std::mem::ManuallyDrop::new(FileChooser { _inner: inner as *mut Fl_File_Chooser });
+        if chooser.shown() {
+            poll_chooser(inner, shared);
+            return;
+        }
+        let mut results = vec![];
+        for i in 1..=chooser.count() {
+            if let Some(v) = chooser.value(i) {
+                results.push(std::path::PathBuf::from(v));
+            }
+        }
+        let mut s = shared.borrow_mut();
+        s.result = Some(results);
+        if let Some(w) = s.waker.take() {
+            w.wake();
+        }
+    });
+}
+
+struct CallbackState {
+    chooser: FileChooser,
+    cb: Option<Box<dyn FnOnce(Vec<std::path::PathBuf>)>>,
+}
+
+fn poll_callback(state: Rc<RefCell<CallbackState>>) {
+    crate::app::add_timeout(0.05, move || {
+        let (done, results) = {
+            let mut st = state.borrow_mut();
+            if st.chooser.shown() {
+                (false, vec![])
+            } else {
+                let mut r = vec![];
+                for i in 1..=st.chooser.count() {
+                    if let Some(v) = st.chooser.value(i) {
+                        r.push(std::path::PathBuf::from(v));
+                    }
+                }
+                (true, r)
+            }
+        };
+        if done {
+            if let Some(cb) = state.borrow_mut().cb.take() {
+                cb(results);
+            }
+        } else {
+            poll_callback(state.clone());
+        }
+    });
+}
+
+/// A future resolving to the single chosen path, or `None` if the dialog was
+/// cancelled. Wraps [`DialogFuture`] and collapses the multi-result vector.
+pub struct SingleDialogFuture {
+    inner: DialogFuture,
+}
+
+impl Future for SingleDialogFuture {
+    type Output = Option<std::path::PathBuf>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.inner).poll(cx) {
+            Poll::Ready(v) => Poll::Ready(v.into_iter().next()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl FileChooser {
+    /// Shows the chooser and returns a future resolving to the single selected
+    /// path, or `None` on cancel — the common single-selection case.
+    pub fn show_async_single(&mut self) -> SingleDialogFuture {
+        SingleDialogFuture {
+            inner: self.show_async(),
+        }
+    }
+
+    /// Shows the chooser and returns a future resolving to the selected paths
+    /// once the user dismisses it, without blocking the main loop.
+    /// The chooser must outlive the returned future.
+    pub fn show_async(&mut self) -> DialogFuture {
+        self.show();
+        let shared = Rc::new(RefCell::new(SharedResult {
+            result: None,
+            waker: None,
+        }));
+        poll_chooser(self._inner as usize, shared.clone());
+        DialogFuture { shared }
+    }
+
+    /// Shows the chooser and invokes `cb` with the selected paths once it is
+    /// dismissed, keeping the main loop running in the meantime.
+    pub fn show_with_callback<F: FnOnce(Vec<std::path::PathBuf>) + 'static>(mut self, cb: F) {
+        self.show();
+        let state = Rc::new(RefCell::new(CallbackState {
+            chooser: self,
+            cb: Some(Box::new(cb)),
+        }));
+        poll_callback(state);
+    }
+}
+
+impl FileDialog {
+    /// Shows the (modal) native dialog and returns an already-resolved future
+    /// with the selected paths, for symmetry with [`FileChooser::show_async`].
+    pub fn show_async(&mut self) -> DialogFuture {
+        self.show();
+        let shared = Rc::new(RefCell::new(SharedResult {
+            result: Some(self.filenames()),
+            waker: None,
+        }));
+        DialogFuture { shared }
+    }
+
+    /// Shows the (modal) native dialog and invokes `cb` with the selected paths.
+    pub fn show_with_callback<F: FnOnce(Vec<std::path::PathBuf>)>(&mut self, cb: F) {
+        self.show();
+        cb(self.filenames());
+    }
+}
+
+/// Metadata for a chosen path, for detail views in custom file dialogs.
+/// Populated by [`stat`] / [`FileDialog::filename_info`].
+#[derive(Clone, Debug)]
+pub struct FileInfo {
+    /// Size in bytes
+    pub size: u64,
+    /// Last modification time
+    pub modified: std::time::SystemTime,
+    /// Whether the path is a directory
+    pub is_dir: bool,
+    /// Whether the owner read bit is set (Unix); always `true` elsewhere
+    pub readable: bool,
+    /// Whether the owner write bit is set (Unix); elsewhere, whether the path
+    /// is not read-only
+    pub writable: bool,
+    /// Owning user name (Unix only; empty elsewhere)
+    pub owner: String,
+    /// Owning group name (Unix only; empty elsewhere)
+    pub group: String,
+}
+
+#[cfg(unix)]
+static UID_CACHE: Mutex<BTreeMap<u32, String>> = Mutex::new(BTreeMap::new());
+#[cfg(unix)]
+static GID_CACHE: Mutex<BTreeMap<u32, String>> = Mutex::new(BTreeMap::new());
+
+/// Resolves an id to a name via `file`, caching the whole file on first use so
+/// repeated lookups don't re-scan. The id is read from the third `:`-separated
+/// column and the name from the first.
+#[cfg(unix)]
+fn resolve_id(cache: &Mutex<BTreeMap<u32, String>>, file: &str, id: u32) -> String {
+    let mut map = cache.lock().unwrap();
+    if map.is_empty() {
+        if let Ok(contents) = std::fs::read_to_string(file) {
+            for line in contents.lines() {
+                let cols: Vec<&str> = line.split(':').collect();
+                if cols.len() >= 3 {
+                    if let Ok(n) = cols[2].parse::<u32>() {
+                        map.entry(n).or_insert_with(|| cols[0].to_string());
+                    }
+                }
+            }
+        }
+    }
+    map.get(&id).cloned().unwrap_or_else(|| id.to_string())
+}
+
+/// Gathers [`FileInfo`] for a path, or `None` if it can't be stat'd. On Unix the
+/// owner/group names are resolved through a process-lifetime id cache.
+pub fn stat(path: &std::path::Path) -> Option<FileInfo> {
+    let md = std::fs::metadata(path).ok()?;
+    let perms = md.permissions();
+    #[allow(unused_mut)]
+    let mut info = FileInfo {
+        size: md.len(),
+        modified: md.modified().unwrap_or(std::time::UNIX_EPOCH),
+        is_dir: md.is_dir(),
+        readable: true,
+        writable: !perms.readonly(),
+        owner: String::new(),
+        group: String::new(),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        use std::os::unix::fs::PermissionsExt;
+        let mode = perms.mode();
+        info.readable = mode & 0o400 != 0;
+        info.writable = mode & 0o200 != 0;
+        info.owner = resolve_id(&UID_CACHE, "/etc/passwd", md.uid());
+        info.group = resolve_id(&GID_CACHE, "/etc/group", md.gid());
+    }
+    Some(info)
+}
+
+impl FileDialog {
+    /// Returns [`FileInfo`] metadata for the currently chosen file, if any
+    pub fn filename_info(&self) -> Option<FileInfo> {
+        stat(&self.filename())
+    }
+}
+
 /// Shows a directory chooser returning a String
 pub fn dir_chooser(message: &str, fname: &str, relative: bool) -> Option<String> {
+    #[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+    if is_sandboxed() {
+        return portal::run(FileDialogType::BrowseDir, message, "")
+            .first()
+            .map(|p| p.to_string_lossy().into_owned());
+    }
     unsafe {
         let message = CString::safe_new(message);
         let fname = CString::safe_new(fname);
@@ -1071,6 +1962,12 @@ pub fn dir_chooser(message: &str, fname: &str, relative: bool) -> Option<String>
 /// println!("{}", file);
 /// ```
 pub fn file_chooser(message: &str, pattern: &str, dir: &str, relative: bool) -> Option<String> {
+    #[cfg(all(feature = "use-portal", any(target_os = "linux", target_os = "freebsd")))]
+    if is_sandboxed() {
+        return portal::run(FileDialogType::BrowseFile, message, pattern)
+            .first()
+            .map(|p| p.to_string_lossy().into_owned());
+    }
     let message = CString::safe_new(message);
     let pattern = CString::safe_new(pattern);
     let dir = CString::safe_new(dir);
@@ -1093,15 +1990,29 @@ pub fn file_chooser(message: &str, pattern: &str, dir: &str, relative: bool) ->
     }
 }
 
-/// Spawns a color_chooser dialog.
-/// `cmode`: Optional mode for color chooser. Default is 0 if rgb mode.
-pub fn color_chooser(name: &str, cmode: i32) -> Option<(u8, u8, u8)> {
+/// The numeric representation shown in the color chooser, matching FLTK's
+/// `Fl_Color_Chooser` input modes.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ColorMode {
+    /// Floating-point RGB in `[0, 1]`
+    Rgb = 0,
+    /// 8-bit per channel RGB
+    Byte = 1,
+    /// Hexadecimal RGB
+    Hex = 2,
+    /// Hue/saturation/value
+    Hsv = 3,
+}
+
+/// Spawns a color chooser dialog, presenting its values in `mode`.
+pub fn color_chooser(name: &str, mode: ColorMode) -> Option<(u8, u8, u8)> {
     unsafe {
         let name = CString::safe_new(name);
         let mut r = 255;
         let mut g = 255;
         let mut b = 255;
-        let ret = Fl_color_chooser(name.as_ptr(), &mut r, &mut g, &mut b, cmode);
+        let ret = Fl_color_chooser(name.as_ptr(), &mut r, &mut g, &mut b, mode as i32);
         if ret == 0 {
             None
         } else {
@@ -1110,15 +2021,14 @@ pub fn color_chooser(name: &str, cmode: i32) -> Option<(u8, u8, u8)> {
     }
 }
 
-/// Spawns a color_chooser dialog.
-/// `cmode`: Optional mode for color chooser. Default is 0 if rgb mode.
-pub fn color_chooser_with_default(name: &str, cmode: i32, col: (u8, u8, u8)) -> (u8, u8, u8) {
+/// Spawns a color chooser dialog seeded with `col`, returning `col` on cancel.
+pub fn color_chooser_with_default(name: &str, mode: ColorMode, col: (u8, u8, u8)) -> (u8, u8, u8) {
     unsafe {
         let name = CString::safe_new(name);
         let mut r = col.0;
         let mut g = col.1;
         let mut b = col.2;
-        let ret = Fl_color_chooser(name.as_ptr(), &mut r, &mut g, &mut b, cmode);
+        let ret = Fl_color_chooser(name.as_ptr(), &mut r, &mut g, &mut b, mode as i32);
         if ret == 0 {
             col
         } else {
@@ -1126,3 +2036,101 @@ pub fn color_chooser_with_default(name: &str, cmode: i32, col: (u8, u8, u8)) ->
         }
     }
 }
+
+/// Spawns a color chooser and surfaces an alpha channel. FLTK's chooser has no
+/// alpha control, so the supplied `alpha` is seeded and round-tripped unchanged
+/// while the RGB components come from the dialog.
+pub fn color_chooser_rgba(name: &str, mode: ColorMode, alpha: u8) -> Option<(u8, u8, u8, u8)> {
+    color_chooser(name, mode).map(|(r, g, b)| (r, g, b, alpha))
+}
+
+/// Converts an RGB triple to an `(h, s, v)` triple with `h` in `[0, 360)` and
+/// `s`/`v` in `[0, 1]`, matching the chooser's HSV readout.
+pub fn rgb_to_hsv(col: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (col.0 as f64 / 255.0, col.1 as f64 / 255.0, col.2 as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let mut h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * (((g - b) / delta) % 6.0)
+    } else if max == g {
+        60.0 * (((b - r) / delta) + 2.0)
+    } else {
+        60.0 * (((r - g) / delta) + 4.0)
+    };
+    if h < 0.0 {
+        h += 360.0;
+    }
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+    (h, s, max)
+}
+
+/// Formats an RGB triple as a `#RRGGBB` hex string.
+pub fn rgb_to_hex(col: (u8, u8, u8)) -> String {
+    format!("#{:02X}{:02X}{:02X}", col.0, col.1, col.2)
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use super::{parse_filters, serialize_filters, FileFilter};
+
+    fn filter(name: &str, pats: &[&str]) -> FileFilter {
+        let mut f = FileFilter::new(name);
+        f.add_patterns(pats);
+        f
+    }
+
+    #[test]
+    fn serialize_folds_shared_prefix() {
+        let filters = [filter("Source", &["*.cxx", "*.rs"]), filter("Text", &["*.txt"])];
+        assert_eq!(
+            serialize_filters(&filters),
+            "Source\t*.{cxx,rs}\nText\t*.txt"
+        );
+    }
+
+    #[test]
+    fn roundtrips_through_encoding() {
+        let filters = vec![
+            filter("Source", &["*.cxx", "*.rs"]),
+            filter("Text", &["*.txt"]),
+        ];
+        let encoded = serialize_filters(&filters);
+        assert_eq!(parse_filters(&encoded), filters);
+    }
+
+    #[test]
+    fn parse_skips_empty_groups() {
+        assert!(parse_filters("").is_empty());
+        assert_eq!(parse_filters("All\t*").len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod wildcard_tests {
+    use super::wildcard_match;
+
+    #[test]
+    fn literal_and_extension_matches() {
+        assert!(wildcard_match("*.txt", "notes.txt"));
+        assert!(!wildcard_match("*.txt", "notes.md"));
+        assert!(wildcard_match("readme", "readme"));
+    }
+
+    #[test]
+    fn star_and_question_semantics() {
+        assert!(wildcard_match("a*c", "abbbc"));
+        assert!(wildcard_match("a*c", "ac"));
+        assert!(!wildcard_match("a*c", "abbb"));
+        assert!(wildcard_match("?.rs", "a.rs"));
+        assert!(!wildcard_match("?.rs", "ab.rs"));
+    }
+
+    #[test]
+    fn trailing_stars_match_empty() {
+        assert!(wildcard_match("abc*", "abc"));
+        assert!(wildcard_match("*", ""));
+    }
+}
@@ -44,6 +44,8 @@ pub type Region = *mut raw::c_void;
 #[derive(Debug)]
 pub struct Offscreen {
     _inner: *mut raw::c_void,
+    w: i32,
+    h: i32,
 }
 
 unsafe impl Sync for Offscreen {}
@@ -58,7 +60,7 @@ impl Offscreen {
             if x.is_null() {
                 None
             } else {
-                Some(Offscreen { _inner: x })
+                Some(Offscreen { _inner: x, w, h })
             }
         }
     }
@@ -69,6 +71,8 @@ impl Offscreen {
     pub unsafe fn uninit() -> Offscreen {
         Offscreen {
             _inner: std::ptr::null_mut(),
+            w: 0,
+            h: 0,
         }
     }
 
@@ -109,6 +113,8 @@ impl Offscreen {
         assert!(!self._inner.is_null());
         Offscreen {
             _inner: self._inner,
+            w: self.w,
+            h: self.h,
         }
     }
 }
@@ -250,6 +256,61 @@ pub fn set_line_style(style: LineStyle, width: i32) {
     }
 }
 
+/// Sets the line style with a custom dash-pattern array.
+/// `dashes` is a list of on/off run lengths in pixels; it is forwarded to
+/// `Fl_line_style` as a null-terminated byte string. A zero-length run is
+/// invalid (it would truncate the pattern) and is rejected.
+pub fn set_line_style_with_dashes(style: LineStyle, width: i32, dashes: &[u8]) {
+    assert!(
+        !dashes.iter().any(|&d| d == 0),
+        "dash segments must be non-zero"
+    );
+    let mut dashes: Vec<u8> = dashes.to_vec();
+    dashes.push(0);
+    unsafe {
+        Fl_line_style(
+            style.bits(),
+            width,
+            dashes.as_mut_ptr() as *mut std::os::raw::c_char,
+        );
+    }
+}
+
+/// RAII guard that sets the line style (cap/join/dash flags, pen `width`, and
+/// an optional custom dash pattern) for the span of a draw, then resets it to
+/// the default `Fl_line_style(0, 0, null)` when dropped. FLTK's line style is
+/// global draw state, so leaving it set corrupts later drawing; the guard makes
+/// the reset automatic.
+///
+/// ```no_run
+/// use fltk::draw::{self, LineStyle};
+/// let _ls = draw::LineStyleGuard::new(LineStyle::Dash | LineStyle::CapRound, 2, None);
+/// draw::draw_line(0, 0, 100, 100);
+/// // style is restored to the default when `_ls` drops
+/// ```
+#[derive(Debug)]
+pub struct LineStyleGuard {
+    _priv: (),
+}
+
+impl LineStyleGuard {
+    /// Applies `style` at pen `width`, optionally with a custom `dashes`
+    /// pattern of on/off run lengths, until the guard drops.
+    pub fn new(style: LineStyle, width: i32, dashes: Option<&[u8]>) -> LineStyleGuard {
+        match dashes {
+            Some(d) => set_line_style_with_dashes(style, width, d),
+            None => set_line_style(style, width),
+        }
+        LineStyleGuard { _priv: () }
+    }
+}
+
+impl Drop for LineStyleGuard {
+    fn drop(&mut self) {
+        set_line_style(LineStyle::Solid, 0);
+    }
+}
+
 /// Limits drawing to a region
 pub fn push_clip(x: i32, y: i32, w: i32, h: i32) {
     unsafe {
@@ -279,6 +340,72 @@ pub fn clip_region() -> Region {
     }
 }
 
+/// Owning wrapper around an FLTK clip [`Region`]. A region can describe a
+/// complex (multi-rectangle) clip that is expensive to rebuild; caching one in
+/// a `ClipRegion` lets a custom widget install the same dirty sub-areas on
+/// every redraw instead of pushing a fresh rectangle stack each `draw`. The
+/// underlying region is freed when the `ClipRegion` drops.
+#[derive(Debug)]
+pub struct ClipRegion {
+    inner: Region,
+}
+
+impl ClipRegion {
+    /// Takes ownership of a raw region pointer.
+    /// # Safety
+    /// `r` must be a non-null region produced by FLTK and not owned elsewhere.
+    pub unsafe fn from_raw(r: Region) -> Option<ClipRegion> {
+        if r.is_null() {
+            None
+        } else {
+            Some(ClipRegion { inner: r })
+        }
+    }
+
+    /// Returns the raw region pointer without transferring ownership.
+    pub fn as_raw(&self) -> Region {
+        self.inner
+    }
+
+    /// Installs this cached region as the current clip, returning a guard that
+    /// restores the previously active region when it drops. Call this at the
+    /// top of a `draw` callback:
+    ///
+    /// ```no_run
+    /// use fltk::draw;
+    /// # fn get_cached() -> draw::ClipRegion { unimplemented!() }
+    /// let cached = get_cached();
+    /// let _guard = cached.install();
+    /// // ... paint the dirty sub-areas; previous region restored on drop ...
+    /// ```
+    pub fn install(&self) -> ClipRegionGuard {
+        let prev = unsafe { Fl_clip_region() };
+        unsafe { Fl_set_clip_region(self.inner) }
+        ClipRegionGuard { prev }
+    }
+}
+
+impl Drop for ClipRegion {
+    fn drop(&mut self) {
+        unsafe { Fl_delete_region(self.inner) }
+    }
+}
+
+/// Guard returned by [`ClipRegion::install`] that restores the clip region that
+/// was active before the installed one when it drops.
+#[derive(Debug)]
+pub struct ClipRegionGuard {
+    prev: Region,
+}
+
+impl Drop for ClipRegionGuard {
+    fn drop(&mut self) {
+        // The previous region may legitimately be null (no region set), so
+        // bypass the non-null assertion of `set_clip_region`.
+        unsafe { Fl_set_clip_region(self.prev) }
+    }
+}
+
 /// Pushes an empty clip region onto the stack so nothing will be clipped
 pub fn push_no_clip() {
     unsafe { Fl_push_no_clip() }
@@ -294,6 +421,53 @@ pub fn restore_clip() {
     unsafe { Fl_restore_clip() }
 }
 
+/// Intersects the rectangle with the current clip region, returning the
+/// bounding box of the intersection as `(x, y, w, h)` along with whether the
+/// rectangle was clipped at all (`true` if it is fully or partially outside the
+/// current region). A zero-sized box means the rectangle is entirely clipped.
+pub fn clip_box(x: i32, y: i32, w: i32, h: i32) -> (i32, i32, i32, i32, bool) {
+    let (mut rx, mut ry, mut rw, mut rh) = (0, 0, 0, 0);
+    let clipped =
+        unsafe { Fl_clip_box(x, y, w, h, &mut rx, &mut ry, &mut rw, &mut rh) != 0 };
+    (rx, ry, rw, rh, clipped)
+}
+
+/// RAII guard that pushes a clip rectangle for custom-draw callbacks and pops
+/// it when dropped, so painting is bounded to the damaged sub-rectangle without
+/// a manual [`pop_clip`] on every code path.
+///
+/// ```no_run
+/// use fltk::draw;
+/// // inside a draw callback, restrict rendering to the widget's bounds
+/// let _clip = draw::ClipGuard::new(x, y, w, h);
+/// // ... draw items, skipping any where draw::not_clipped(..) is false ...
+/// ```
+#[derive(Debug)]
+pub struct ClipGuard {
+    _priv: (),
+}
+
+impl ClipGuard {
+    /// Pushes a clip rectangle, restricting drawing to it until the guard drops.
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> ClipGuard {
+        push_clip(x, y, w, h);
+        ClipGuard { _priv: () }
+    }
+
+    /// Pushes an empty (no-op) clip region, so nothing is clipped until the
+    /// guard drops.
+    pub fn no_clip() -> ClipGuard {
+        push_no_clip();
+        ClipGuard { _priv: () }
+    }
+}
+
+impl Drop for ClipGuard {
+    fn drop(&mut self) {
+        pop_clip();
+    }
+}
+
 /// Transforms coordinate using the current transformation matrix
 pub fn transform_x(x: f64, y: f64) -> f64 {
     unsafe { Fl_transform_x(x, y) }
@@ -823,10 +997,12 @@ pub fn write_to_png_file<I: ImageExt, P: AsRef<std::path::Path>>(
 }
 
 fn write_to_png_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<(), FltkError> {
-    assert!(
-        std::any::type_name::<I>() != std::any::type_name::<crate::image::SvgImage>(),
-        "SVG images are not supported!"
-    );
+    // A vector source has no intrinsic raster data; rasterize it at its nominal
+    // size and write that instead of panicking.
+    if is_svg::<I>() {
+        let raster = rasterize(image, 0, 0)?;
+        return write_to_png_file_(&raster, path);
+    }
     let path = path.to_str();
     if path.is_none() {
         return Err(FltkError::IoError(std::io::Error::new(
@@ -851,19 +1027,64 @@ fn write_to_png_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<
     }
 }
 
+/// Whether the concrete image type is an `SvgImage`
+fn is_svg<I: ImageExt>() -> bool {
+    std::any::type_name::<I>() == std::any::type_name::<crate::image::SvgImage>()
+}
+
+/// Rasterizes an image to an `RgbImage`. When `w`/`h` are positive the raster is
+/// produced at that resolution, otherwise at the image's intrinsic size. This is
+/// used to export vector sources at an explicit raster size.
+fn rasterize<I: ImageExt>(image: &I, w: i32, h: i32) -> Result<crate::image::RgbImage, FltkError> {
+    let copy = image.to_rgb()?;
+    if w > 0 && h > 0 {
+        return resample(&copy, w, h);
+    }
+    Ok(copy)
+}
+
+/// Writes an `SvgImage` (or any image) to a PNG rasterized at an explicit
+/// `w`×`h` resolution rather than its intrinsic one.
+pub fn write_to_png_file_scaled<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+    w: i32,
+    h: i32,
+) -> Result<(), FltkError> {
+    let raster = rasterize(image, w, h)?;
+    write_to_png_file_(&raster, path.as_ref())
+}
+
 /// Transforms raw data to jpg file
 pub fn write_to_jpg_file<I: ImageExt, P: AsRef<std::path::Path>>(
     image: &I,
     path: P,
 ) -> Result<(), FltkError> {
-    write_to_jpg_file_(image, path.as_ref())
+    write_to_jpg_file_(image, path.as_ref(), 90)
 }
 
-fn write_to_jpg_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<(), FltkError> {
-    assert!(
-        std::any::type_name::<I>() != std::any::type_name::<crate::image::SvgImage>(),
-        "SVG images are not supported!"
-    );
+/// Transforms raw data to a jpg file with a `quality` in 1..=100, trading file
+/// size against fidelity. `quality` is clamped into range.
+pub fn write_to_jpg_file_with_quality<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+    quality: u8,
+) -> Result<(), FltkError> {
+    write_to_jpg_file_(image, path.as_ref(), quality)
+}
+
+fn write_to_jpg_file_<I: ImageExt>(
+    image: &I,
+    path: &std::path::Path,
+    quality: u8,
+) -> Result<(), FltkError> {
+    // A vector source has no intrinsic raster data; rasterize it at its nominal
+    // size and write that instead of panicking.
+    if is_svg::<I>() {
+        let raster = rasterize(image, 0, 0)?;
+        return write_to_jpg_file_(&raster, path, quality);
+    }
+    let quality = quality.clamp(1, 100);
     let path = path.to_str();
     if path.is_none() {
         return Err(FltkError::IoError(std::io::Error::new(
@@ -873,11 +1094,12 @@ fn write_to_jpg_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<
     }
     let path = std::ffi::CString::new(path.unwrap())?;
     unsafe {
-        match Fl_raw_image_to_jpg(
+        match Fl_raw_image_to_jpg_quality(
             *image.to_raw_data() as *mut u8,
             path.as_ptr(),
             image.data_w() as i32,
             image.data_h() as i32,
+            quality as i32,
         ) {
             -1 => Err(FltkError::IoError(std::io::Error::new(
                 std::io::ErrorKind::Other,
@@ -924,3 +1146,1402 @@ fn write_to_bmp_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<
         }
     }
 }
+
+/// Defines how a gradient behaves outside its `[0, 1]` position range
+#[repr(i32)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientExtend {
+    /// Clamp to the first/last stop
+    Clamp = 0,
+    /// Repeat the gradient
+    Repeat = 1,
+    /// Repeat the gradient, mirroring every other span
+    Reflect = 2,
+}
+
+/// A gradient defined by a sorted list of position/color stops.
+/// Since FLTK has no native gradient, the fill is computed on the CPU into an
+/// `RgbImage` and blitted through the normal image path, so it respects the
+/// current clip region.
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    stops: Vec<(f32, (u8, u8, u8))>,
+    extend: GradientExtend,
+}
+
+impl Gradient {
+    /// Creates a gradient from sorted position/color `stops` and an `extend` mode
+    pub fn new(stops: &[(f32, Color)], extend: GradientExtend) -> Gradient {
+        let stops = stops
+            .iter()
+            .map(|(t, c)| (*t, c.to_rgb()))
+            .collect::<Vec<_>>();
+        Gradient { stops, extend }
+    }
+
+    /// Applies the extend mode, wrapping `t` into `[0, 1]`
+    fn wrap(&self, t: f32) -> f32 {
+        match self.extend {
+            GradientExtend::Clamp => t.clamp(0.0, 1.0),
+            GradientExtend::Repeat => t - t.floor(),
+            GradientExtend::Reflect => {
+                let f = (t * 0.5).rem_euclid(1.0) * 2.0;
+                if f > 1.0 {
+                    2.0 - f
+                } else {
+                    f
+                }
+            }
+        }
+    }
+
+    /// Binary-searches the bracketing stops and linearly interpolates each channel
+    fn sample(&self, t: f32) -> (u8, u8, u8) {
+        if self.stops.is_empty() {
+            return (0, 0, 0);
+        }
+        let t = self.wrap(t);
+        if t <= self.stops[0].0 {
+            return self.stops[0].1;
+        }
+        if t >= self.stops[self.stops.len() - 1].0 {
+            return self.stops[self.stops.len() - 1].1;
+        }
+        let hi = self.stops.partition_point(|s| s.0 < t);
+        let (t0, a) = self.stops[hi - 1];
+        let (t1, b) = self.stops[hi];
+        let frac = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * frac).round() as u8;
+        (lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+    }
+
+    /// Rasterizes the gradient into an `RgbImage` of the given size using the `axis`
+    /// closure, which maps a pixel `(col, row)` to a parametric position `t`
+    fn rasterize<F: Fn(i32, i32) -> f32>(&self, w: i32, h: i32, axis: F) -> Option<RgbImage> {
+        if w <= 0 || h <= 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; (w * h * 3) as usize];
+        for row in 0..h {
+            for col in 0..w {
+                let (r, g, b) = self.sample(axis(col, row));
+                let idx = ((row * w + col) * 3) as usize;
+                buf[idx] = r;
+                buf[idx + 1] = g;
+                buf[idx + 2] = b;
+            }
+        }
+        RgbImage::new(&buf, w as u32, h as u32, ColorDepth::Rgb8).ok()
+    }
+}
+
+/// Fills the rectangle `(x, y, w, h)` with a linear gradient along its axis.
+/// `stops` are sorted position/color pairs and `extend` wraps the gradient.
+pub fn draw_linear_gradient(
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    stops: &[(f32, Color)],
+    extend: GradientExtend,
+) {
+    let grad = Gradient::new(stops, extend);
+    // Axis runs top-to-bottom; each scanline shares a single parametric position.
+    let denom = (h - 1).max(1) as f32;
+    if let Some(mut img) = grad.rasterize(w, h, |_, row| row as f32 / denom) {
+        img.draw(x, y, w, h);
+    }
+}
+
+/// Fills a `2*r` square centered at `(cx, cy)` with a radial gradient.
+/// `t` is the distance from the center divided by `r`.
+pub fn draw_radial_gradient(
+    cx: i32,
+    cy: i32,
+    r: i32,
+    stops: &[(f32, Color)],
+    extend: GradientExtend,
+) {
+    let grad = Gradient::new(stops, extend);
+    let d = r.max(1) as f32;
+    let side = r * 2;
+    if let Some(mut img) = grad.rasterize(side, side, |col, row| {
+        let dx = col as f32 - r as f32;
+        let dy = row as f32 - r as f32;
+        (dx * dx + dy * dy).sqrt() / d
+    }) {
+        img.draw(cx - r, cy - r, side, side);
+    }
+}
+
+/// Caches a gradient into an `Offscreen` of the given size so it can be blitted
+/// repeatedly without recomputing the CPU fill.
+pub fn cache_linear_gradient(
+    w: i32,
+    h: i32,
+    stops: &[(f32, Color)],
+    extend: GradientExtend,
+) -> Option<Offscreen> {
+    let off = Offscreen::new(w, h)?;
+    off.begin();
+    draw_linear_gradient(0, 0, w, h, stops, extend);
+    off.end();
+    Some(off)
+}
+
+/// Separable blend modes, borrowing the set from raqote, used when compositing
+/// one `RgbImage`/`Offscreen` onto another.
+#[repr(i32)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BlendMode {
+    /// Replace the destination with the source
+    Src = 0,
+    /// Standard source-over (the FLTK default)
+    SrcOver = 1,
+    /// Multiply the channels
+    Multiply = 2,
+    /// Screen the channels
+    Screen = 3,
+    /// Overlay (HardLight with swapped operands)
+    Overlay = 4,
+    /// Keep the darker channel
+    Darken = 5,
+    /// Keep the lighter channel
+    Lighten = 6,
+    /// Color dodge
+    ColorDodge = 7,
+    /// Color burn
+    ColorBurn = 8,
+    /// Hard light
+    HardLight = 9,
+    /// Absolute channel difference
+    Difference = 10,
+    /// Additive blend
+    Add = 11,
+    /// Exclusive-or coverage
+    Xor = 12,
+}
+
+impl BlendMode {
+    /// Computes the separable blend of a source and destination channel (0..=255)
+    fn blend(self, cs: i32, cd: i32) -> i32 {
+        let out = match self {
+            BlendMode::Multiply => cs * cd / 255,
+            BlendMode::Screen => 255 - (255 - cs) * (255 - cd) / 255,
+            BlendMode::Overlay => BlendMode::HardLight.blend(cd, cs),
+            BlendMode::Darken => cs.min(cd),
+            BlendMode::Lighten => cs.max(cd),
+            BlendMode::ColorDodge => {
+                if cs >= 255 {
+                    255
+                } else {
+                    (cd * 255 / (255 - cs)).min(255)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if cs <= 0 {
+                    0
+                } else {
+                    255 - ((255 - cd) * 255 / cs).min(255)
+                }
+            }
+            BlendMode::HardLight => {
+                if cs <= 127 {
+                    cs * cd * 2 / 255
+                } else {
+                    255 - (255 - cd) * (255 - (2 * cs - 255)) / 255
+                }
+            }
+            BlendMode::Difference => (cs - cd).abs(),
+            BlendMode::Add => cs + cd,
+            // Src/SrcOver/Xor don't modify the blended channel itself
+            _ => cs,
+        };
+        out.clamp(0, 255)
+    }
+}
+
+/// Composites `src` onto `dst` at offset `(x, y)` using the given blend `mode`,
+/// operating on 8-bit channels with the standard Porter-Duff "over" weighting.
+pub fn composite(dst: &mut RgbImage, src: &RgbImage, x: i32, y: i32, mode: BlendMode) {
+    let dw = dst.data_w() as i32;
+    let dh = dst.data_h() as i32;
+    let sw = src.data_w() as i32;
+    let sh = src.data_h() as i32;
+    let sd = src.depth() as i32;
+    let dd = dst.depth() as i32;
+    let src_data = src.to_rgb_data();
+    let mut dst_data = dst.to_rgb_data();
+    for row in 0..sh {
+        let dy = y + row;
+        if dy < 0 || dy >= dh {
+            continue;
+        }
+        for col in 0..sw {
+            let dx = x + col;
+            if dx < 0 || dx >= dw {
+                continue;
+            }
+            let si = ((row * sw + col) * sd) as usize;
+            let di = ((dy * dw + dx) * dd) as usize;
+            let sa = if sd == 4 { src_data[si + 3] as i32 } else { 255 };
+            let da = if dd == 4 { dst_data[di + 3] as i32 } else { 255 };
+            for c in 0..3 {
+                let cs = src_data[si + c] as i32;
+                let cd = dst_data[di + c] as i32;
+                let b = mode.blend(cs, cd);
+                // source-weighted blend result composited over the destination
+                let out = match mode {
+                    BlendMode::Src => cs,
+                    BlendMode::Xor => (cs * (255 - da) + cd * (255 - sa)) / 255,
+                    _ => (b * sa * da + cs * sa * (255 - da) + cd * da * (255 - sa)) / (255 * 255),
+                };
+                dst_data[di + c] = out.clamp(0, 255) as u8;
+            }
+            if dd == 4 {
+                let out_a = sa + da * (255 - sa) / 255;
+                dst_data[di + 3] = out_a.clamp(0, 255) as u8;
+            }
+        }
+    }
+    if let Ok(img) = RgbImage::new(&dst_data, dw as u32, dh as u32, dst.depth()) {
+        *dst = img;
+    }
+}
+
+impl Offscreen {
+    /// Reads this offscreen's pixels back into an `RgbImage`.
+    fn read_back(&self) -> Option<RgbImage> {
+        self.begin();
+        let cp = (self.w * self.h * 3) as usize;
+        let img = unsafe {
+            let ptr = Fl_read_image(std::ptr::null_mut(), 0, 0, self.w, self.h, 0);
+            if ptr.is_null() {
+                None
+            } else {
+                let data = std::slice::from_raw_parts(ptr, cp);
+                RgbImage::new(data, self.w as u32, self.h as u32, ColorDepth::Rgb8).ok()
+            }
+        };
+        self.end();
+        img
+    }
+
+    /// Composites the contents of `other` onto this offscreen using `mode`.
+    /// Both offscreens are read back into `RgbImage`s, blended on the CPU, and
+    /// the result is drawn back into this offscreen.
+    pub fn composite_from(&self, other: &Offscreen, mode: BlendMode) {
+        assert!(!self._inner.is_null() && !other._inner.is_null());
+        if let (Some(mut dst), Some(src)) = (self.read_back(), other.read_back()) {
+            composite(&mut dst, &src, 0, 0, mode);
+            self.begin();
+            let _ = draw_image(&dst.to_rgb_data(), 0, 0, self.w, self.h, dst.depth());
+            self.end();
+        }
+    }
+}
+
+/// Fill winding rule for a `Path`
+#[repr(i32)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Winding {
+    /// Non-zero winding rule
+    NonZero = 0,
+    /// Even-odd winding rule
+    EvenOdd = 1,
+}
+
+enum PathVerb {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    CubicTo(f64, f64, f64, f64, f64, f64),
+    QuadTo(f64, f64, f64, f64),
+    Close,
+}
+
+/// A retained-mode geometry builder that records path segments and renders them
+/// by flattening into the global vertex calls under the hood, avoiding the
+/// fragile implicit ordering of the raw `begin_*`/`vertex`/`end_*` API.
+pub struct Path {
+    verbs: Vec<PathVerb>,
+    winding: Winding,
+}
+
+impl Default for Path {
+    fn default() -> Path {
+        Path::new()
+    }
+}
+
+impl Path {
+    /// Creates an empty path with the non-zero winding rule
+    pub fn new() -> Path {
+        Path {
+            verbs: vec![],
+            winding: Winding::NonZero,
+        }
+    }
+
+    /// Sets the winding rule used when filling
+    pub fn set_winding(&mut self, winding: Winding) {
+        self.winding = winding;
+    }
+
+    /// Begins a new subpath at `(x, y)`
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.verbs.push(PathVerb::MoveTo(x, y));
+    }
+
+    /// Adds a straight segment to `(x, y)`
+    pub fn line_to(&mut self, x: f64, y: f64) {
+        self.verbs.push(PathVerb::LineTo(x, y));
+    }
+
+    /// Adds a cubic Bézier through control points `c1`, `c2` to `end`
+    pub fn cubic_to(&mut self, c1: Coord<f64>, c2: Coord<f64>, end: Coord<f64>) {
+        self.verbs
+            .push(PathVerb::CubicTo(c1.0, c1.1, c2.0, c2.1, end.0, end.1));
+    }
+
+    /// Adds a quadratic Bézier through control point `c` to `end`
+    pub fn quad_to(&mut self, c: Coord<f64>, end: Coord<f64>) {
+        self.verbs.push(PathVerb::QuadTo(c.0, c.1, end.0, end.1));
+    }
+
+    /// Adds an arc segment, sampled into straight line segments of at most 6 degrees
+    pub fn arc_to(&mut self, cx: f64, cy: f64, r: f64, start: f64, end: f64) {
+        // sample the arc into line segments so it flattens with everything else
+        let steps = ((end - start).abs() / 6.0).ceil().max(2.0) as i32;
+        for i in 0..=steps {
+            let a = (start + (end - start) * i as f64 / steps as f64).to_radians();
+            self.verbs
+                .push(PathVerb::LineTo(cx + r * a.cos(), cy - r * a.sin()));
+        }
+    }
+
+    /// Closes the current subpath
+    pub fn close(&mut self) {
+        self.verbs.push(PathVerb::Close);
+    }
+
+    /// Recursively subdivides a cubic at t=0.5 until it is flat enough, emitting
+    /// `vertex` calls. Flatness is the max distance of the control points from
+    /// the chord.
+    fn flatten_cubic(
+        x0: f64,
+        y0: f64,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        x3: f64,
+        y3: f64,
+    ) {
+        let d1 = point_line_distance(x1, y1, x0, y0, x3, y3);
+        let d2 = point_line_distance(x2, y2, x0, y0, x3, y3);
+        if d1.max(d2) < 0.1 {
+            vertex(x3, y3);
+            return;
+        }
+        let (ax, ay) = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+        let (bx, by) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+        let (cx, cy) = ((x2 + x3) / 2.0, (y2 + y3) / 2.0);
+        let (dx, dy) = ((ax + bx) / 2.0, (ay + by) / 2.0);
+        let (ex, ey) = ((bx + cx) / 2.0, (by + cy) / 2.0);
+        let (fx, fy) = ((dx + ex) / 2.0, (dy + ey) / 2.0);
+        Path::flatten_cubic(x0, y0, ax, ay, dx, dy, fx, fy);
+        Path::flatten_cubic(fx, fy, ex, ey, cx, cy, x3, y3);
+    }
+
+    /// Emits the whole path through FLTK's vertex list, with a `gap` at each
+    /// `close`; a `move_to` on its own emits no `gap`.
+    fn emit(&self) {
+        Path::emit_verbs(&self.verbs);
+    }
+
+    /// Splits the recorded verbs into one slice per subpath, breaking before
+    /// every `MoveTo` after the first. Used to fill contours independently for
+    /// the non-zero winding rule.
+    fn contours(&self) -> Vec<&[PathVerb]> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        for (i, verb) in self.verbs.iter().enumerate() {
+            if i > start && matches!(verb, PathVerb::MoveTo(..)) {
+                out.push(&self.verbs[start..i]);
+                start = i;
+            }
+        }
+        if start < self.verbs.len() {
+            out.push(&self.verbs[start..]);
+        }
+        out
+    }
+
+    /// Emits a run of verbs through FLTK's vertex list.
+    fn emit_verbs(verbs: &[PathVerb]) {
+        let (mut px, mut py) = (0.0f64, 0.0f64);
+        for verb in verbs {
+            match *verb {
+                PathVerb::MoveTo(x, y) => {
+                    vertex(x, y);
+                    px = x;
+                    py = y;
+                }
+                PathVerb::LineTo(x, y) => {
+                    vertex(x, y);
+                    px = x;
+                    py = y;
+                }
+                PathVerb::CubicTo(c1x, c1y, c2x, c2y, ex, ey) => {
+                    Path::flatten_cubic(px, py, c1x, c1y, c2x, c2y, ex, ey);
+                    px = ex;
+                    py = ey;
+                }
+                PathVerb::QuadTo(cx, cy, ex, ey) => {
+                    // elevate the quadratic to a cubic then flatten
+                    let c1x = px + 2.0 / 3.0 * (cx - px);
+                    let c1y = py + 2.0 / 3.0 * (cy - py);
+                    let c2x = ex + 2.0 / 3.0 * (cx - ex);
+                    let c2y = ey + 2.0 / 3.0 * (cy - ey);
+                    Path::flatten_cubic(px, py, c1x, c1y, c2x, c2y, ex, ey);
+                    px = ex;
+                    py = ey;
+                }
+                PathVerb::Close => gap(),
+            }
+        }
+    }
+
+    /// Fills the path with `color`, honoring the winding rule.
+    ///
+    /// FLTK's complex-polygon fill treats the gaps between subpaths with the
+    /// even-odd rule, so nested contours punch holes. That is exactly
+    /// `Winding::EvenOdd`. For `Winding::NonZero` we instead fill each subpath
+    /// on its own, so overlapping contours union into a solid shape rather than
+    /// cancelling out.
+    pub fn fill(&self, color: Color) {
+        set_draw_color(color);
+        match self.winding {
+            Winding::EvenOdd => {
+                begin_complex_polygon();
+                self.emit();
+                end_complex_polygon();
+            }
+            Winding::NonZero => {
+                for contour in self.contours() {
+                    begin_complex_polygon();
+                    Path::emit_verbs(contour);
+                    end_complex_polygon();
+                }
+            }
+        }
+    }
+
+    /// Strokes the path outline with `color` and pen `width`
+    pub fn stroke(&self, color: Color, width: i32) {
+        set_draw_color(color);
+        set_line_style(LineStyle::Solid, width);
+        begin_line();
+        self.emit();
+        end_line();
+        set_line_style(LineStyle::Solid, 0);
+    }
+}
+
+/// Perpendicular distance of `(px, py)` from the line through `(ax, ay)`-`(bx, by)`
+fn point_line_distance(px: f64, py: f64, ax: f64, ay: f64, bx: f64, by: f64) -> f64 {
+    let dx = bx - ax;
+    let dy = by - ay;
+    let len = (dx * dx + dy * dy).sqrt();
+    if len == 0.0 {
+        ((px - ax).powi(2) + (py - ay).powi(2)).sqrt()
+    } else {
+        ((px - ax) * dy - (py - ay) * dx).abs() / len
+    }
+}
+
+/// Emits a quarter-circle arc from `start` to `start + 90°` centered at
+/// `(cx, cy)` with the given `radius`, as a series of `vertex` calls.
+fn rounded_corner(cx: f64, cy: f64, radius: f64, start_deg: f64) {
+    let steps = ((radius / 2.0) as i32).max(3);
+    for i in 0..=steps {
+        let a = (start_deg + 90.0 * i as f64 / steps as f64).to_radians();
+        vertex(cx + radius * a.cos(), cy - radius * a.sin());
+    }
+}
+
+/// Walks the four edges and corners of a rounded rectangle, calling `vertex`.
+/// `radius` is clamped to `min(w, h) / 2`.
+fn rounded_rect_path(x: i32, y: i32, w: i32, h: i32, radius: i32) {
+    let r = radius.min(w.min(h) / 2).max(0) as f64;
+    let (x, y, w, h) = (x as f64, y as f64, w as f64, h as f64);
+    // top-right, top-left, bottom-left, bottom-right corner centers, in the
+    // counterclockwise order each corner arc is walked in
+    rounded_corner(x + w - r, y + r, r, 0.0);
+    rounded_corner(x + r, y + r, r, 90.0);
+    rounded_corner(x + r, y + h - r, r, 180.0);
+    rounded_corner(x + w - r, y + h - r, r, 270.0);
+}
+
+/// Draws the outline of an anti-aliased rounded rectangle
+pub fn draw_rounded_rect(x: i32, y: i32, w: i32, h: i32, radius: i32) {
+    begin_loop();
+    rounded_rect_path(x, y, w, h, radius);
+    end_loop();
+}
+
+/// Draws a filled anti-aliased rounded rectangle with the given `color`
+pub fn draw_rounded_rectf(x: i32, y: i32, w: i32, h: i32, radius: i32, color: Color) {
+    set_draw_color(color);
+    begin_complex_polygon();
+    rounded_rect_path(x, y, w, h, radius);
+    end_complex_polygon();
+}
+
+/// QR error-correction level, trading capacity against recoverability
+#[repr(i32)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum QrEcLevel {
+    /// ~7% recovery
+    Low = 0,
+    /// ~15% recovery
+    Medium = 1,
+    /// ~25% recovery
+    Quartile = 2,
+    /// ~30% recovery
+    High = 3,
+}
+
+/// Width in modules of the light margin surrounding the symbol, as mandated by
+/// the QR spec so scanners can isolate the code from its surroundings.
+const QR_QUIET_ZONE: i32 = 4;
+
+/// Encodes `data` into a QR bit matrix and rasterizes it with `draw_rect_fill`,
+/// one `module_size`-pixel square per set module. The background is painted once
+/// with `bg`, including a 4-module quiet zone around the symbol so scanners can
+/// decode it against an arbitrary widget background. Returns the pixel side
+/// length of the rendered code including the quiet zone, or `None` if the data
+/// does not fit a single-block version (see the `qr` module).
+pub fn draw_qr(
+    data: &str,
+    x: i32,
+    y: i32,
+    module_size: i32,
+    fg: Color,
+    bg: Color,
+    level: QrEcLevel,
+) -> Option<i32> {
+    let matrix = qr::encode(data.as_bytes(), level)?;
+    let n = matrix.len() as i32;
+    let margin = QR_QUIET_ZONE * module_size;
+    let dim = n * module_size + 2 * margin;
+    draw_rect_fill(x, y, dim, dim, bg);
+    set_draw_color(fg);
+    for (row, line) in matrix.iter().enumerate() {
+        for (col, &on) in line.iter().enumerate() {
+            if on {
+                draw_rectf(
+                    x + margin + col as i32 * module_size,
+                    y + margin + row as i32 * module_size,
+                    module_size,
+                    module_size,
+                );
+            }
+        }
+    }
+    Some(dim)
+}
+
+/// A minimal byte-mode QR encoder. Only single-block layouts are encoded:
+/// versions 1–5 at `Low`, 1–3 at `Medium` and 1–2 at `Quartile` and `High`;
+/// layouts with several error-correction blocks are not supported. It builds
+/// the module matrix only; rasterization is left to `draw_qr`.
+mod qr {
+    use super::QrEcLevel;
+
+    // Galois field GF(256) log/antilog tables for Reed-Solomon, generator 0x11d.
+    struct Gf {
+        exp: [u8; 512],
+        log: [u8; 256],
+    }
+
+    fn gf() -> Gf {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+        let mut x: u16 = 1;
+        for i in 0..255 {
+            exp[i] = x as u8;
+            log[x as usize] = i as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11d;
+            }
+        }
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+        Gf { exp, log }
+    }
+
+    fn gf_mul(g: &Gf, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            g.exp[g.log[a as usize] as usize + g.log[b as usize] as usize]
+        }
+    }
+
+    /// Builds the Reed-Solomon generator polynomial of the given degree
+    fn rs_generator(g: &Gf, degree: usize) -> Vec<u8> {
+        let mut poly = vec![1u8];
+        for i in 0..degree {
+            let mut next = vec![0u8; poly.len() + 1];
+            for (j, &c) in poly.iter().enumerate() {
+                next[j] ^= c;
+                next[j + 1] ^= gf_mul(g, c, g.exp[i]);
+            }
+            poly = next;
+        }
+        poly
+    }
+
+    /// Computes `ec_len` error-correction codewords for `data`
+    fn rs_encode(g: &Gf, data: &[u8], ec_len: usize) -> Vec<u8> {
+        let gen = rs_generator(g, ec_len);
+        let mut res = vec![0u8; ec_len];
+        for &d in data {
+            let factor = d ^ res[0];
+            res.remove(0);
+            res.push(0);
+            for (i, &gc) in gen.iter().skip(1).enumerate() {
+                res[i] ^= gf_mul(g, gc, factor);
+            }
+        }
+        res
+    }
+
+    // (version, level) -> (total data codewords, ec codewords per block, block count)
+    // Layouts for versions 1–10; `encode` only uses the entries with one block.
+    fn spec(version: usize, level: QrEcLevel) -> Option<(usize, usize, usize)> {
+        // data codewords summed over all blocks, per the QR spec
+        let table: &[[(usize, usize, usize); 4]] = &[
+            // L, M, Q, H
+            [(19, 7, 1), (16, 10, 1), (13, 13, 1), (9, 17, 1)], // v1
+            [(34, 10, 1), (28, 16, 1), (22, 22, 1), (16, 28, 1)], // v2
+            [(55, 15, 1), (44, 26, 1), (34, 18, 2), (26, 22, 2)], // v3
+            [(80, 20, 1), (64, 18, 2), (48, 26, 2), (36, 16, 4)], // v4
+            [(108, 26, 1), (86, 24, 2), (62, 18, 4), (46, 22, 4)], // v5
+            [(136, 18, 2), (108, 16, 4), (76, 24, 4), (60, 28, 4)], // v6
+            [(156, 20, 2), (124, 18, 4), (88, 18, 6), (66, 26, 5)], // v7
+            [(194, 24, 2), (154, 22, 4), (110, 22, 6), (86, 26, 6)], // v8
+            [(232, 30, 2), (182, 22, 5), (132, 20, 8), (100, 24, 8)], // v9
+            [(274, 18, 4), (216, 26, 5), (154, 24, 8), (122, 28, 8)], // v10
+        ];
+        let row = table.get(version - 1)?;
+        let (data, ec, blocks) = row[level as usize];
+        Some((data, ec, blocks))
+    }
+
+    fn module_count(version: usize) -> usize {
+        17 + version * 4
+    }
+
+    /// Encodes `data` in byte mode, returning the module matrix (true = dark),
+    /// or `None` if it doesn't fit any single-block version.
+    pub fn encode(data: &[u8], level: QrEcLevel) -> Option<Vec<Vec<bool>>> {
+        let g = gf();
+        // pick the smallest version whose (single-block) data capacity fits
+        let (version, total_data, ec_len, _blocks) = (1..=10).find_map(|v| {
+            let (total, ec, blocks) = spec(v, level)?;
+            // byte mode: 4 mode bits + char-count bits + 8 bits/byte
+            let cc_bits = if v <= 9 { 8 } else { 16 };
+            let needed = (4 + cc_bits + data.len() * 8 + 7) / 8;
+            if blocks == 1 && needed <= total {
+                Some((v, total, ec, blocks))
+            } else {
+                None
+            }
+        })?;
+
+        // assemble the bit stream
+        let mut bits: Vec<bool> = Vec::new();
+        let mut push = |val: usize, len: usize, bits: &mut Vec<bool>| {
+            for i in (0..len).rev() {
+                bits.push((val >> i) & 1 == 1);
+            }
+        };
+        push(0b0100, 4, &mut bits); // byte mode
+        let cc_bits = if version <= 9 { 8 } else { 16 };
+        push(data.len(), cc_bits, &mut bits);
+        for &b in data {
+            push(b as usize, 8, &mut bits);
+        }
+        // terminator + byte alignment
+        let capacity_bits = total_data * 8;
+        for _ in 0..4.min(capacity_bits.saturating_sub(bits.len())) {
+            bits.push(false);
+        }
+        while bits.len() % 8 != 0 {
+            bits.push(false);
+        }
+        // pad bytes
+        let pads = [0xECu8, 0x11];
+        let mut pi = 0;
+        let mut data_cw: Vec<u8> = bits
+            .chunks(8)
+            .map(|c| c.iter().fold(0u8, |a, &b| (a << 1) | b as u8))
+            .collect();
+        while data_cw.len() < total_data {
+            data_cw.push(pads[pi % 2]);
+            pi += 1;
+        }
+        let ec_cw = rs_encode(&g, &data_cw, ec_len);
+
+        // final codeword sequence (single block)
+        let mut all = data_cw;
+        all.extend_from_slice(&ec_cw);
+
+        let n = module_count(version);
+        let mut m = vec![vec![false; n]; n];
+        let mut reserved = vec![vec![false; n]; n];
+        place_function_patterns(&mut m, &mut reserved, version);
+
+        // place data bits in zig-zag, skipping reserved modules
+        let mut data_bits: Vec<bool> = Vec::new();
+        for cw in &all {
+            for i in (0..8).rev() {
+                data_bits.push((cw >> i) & 1 == 1);
+            }
+        }
+        let mut di = 0;
+        let mut col = n as i32 - 1;
+        let mut upward = true;
+        while col > 0 {
+            if col == 6 {
+                col -= 1; // skip vertical timing column
+            }
+            let rows: Vec<i32> = if upward {
+                (0..n as i32).rev().collect()
+            } else {
+                (0..n as i32).collect()
+            };
+            for row in rows {
+                for c in [col, col - 1] {
+                    let (r, c) = (row as usize, c as usize);
+                    if !reserved[r][c] {
+                        let bit = data_bits.get(di).copied().unwrap_or(false);
+                        m[r][c] = bit;
+                        di += 1;
+                    }
+                }
+            }
+            upward = !upward;
+            col -= 2;
+        }
+
+        // pick the best mask by penalty score
+        let mut best: Option<(i32, Vec<Vec<bool>>)> = None;
+        for mask in 0..8 {
+            let mut cand = m.clone();
+            apply_mask(&mut cand, &reserved, mask);
+            place_format_info(&mut cand, level, mask);
+            let score = penalty(&cand);
+            if best.as_ref().map(|(s, _)| score < *s).unwrap_or(true) {
+                best = Some((score, cand));
+            }
+        }
+        best.map(|(_, m)| m)
+    }
+
+    fn set(m: &mut [Vec<bool>], res: &mut [Vec<bool>], r: usize, c: usize, v: bool) {
+        m[r][c] = v;
+        res[r][c] = true;
+    }
+
+    fn place_finder(m: &mut [Vec<bool>], res: &mut [Vec<bool>], r: i32, c: i32) {
+        for dr in -1..=7 {
+            for dc in -1..=7 {
+                let (rr, cc) = (r + dr, c + dc);
+                if rr < 0 || cc < 0 || rr as usize >= m.len() || cc as usize >= m.len() {
+                    continue;
+                }
+                let border = dr == -1 || dr == 7 || dc == -1 || dc == 7;
+                let ring = (0..=6).contains(&dr) && (0..=6).contains(&dc);
+                let dark = ring
+                    && (dr == 0
+                        || dr == 6
+                        || dc == 0
+                        || dc == 6
+                        || ((2..=4).contains(&dr) && (2..=4).contains(&dc)));
+                set(m, res, rr as usize, cc as usize, dark && !border);
+            }
+        }
+    }
+
+    fn place_function_patterns(m: &mut [Vec<bool>], res: &mut [Vec<bool>], version: usize) {
+        let n = m.len() as i32;
+        place_finder(m, res, 0, 0);
+        place_finder(m, res, 0, n - 7);
+        place_finder(m, res, n - 7, 0);
+        // timing patterns
+        for i in 8..(n - 8) {
+            let v = i % 2 == 0;
+            set(m, res, 6, i as usize, v);
+            set(m, res, i as usize, 6, v);
+        }
+        // dark module
+        set(m, res, (4 * version + 9) as usize, 8, true);
+        // reserve format info areas
+        for i in 0..9 {
+            if i != 6 {
+                res[8][i] = true;
+                res[i][8] = true;
+            }
+        }
+        for i in 0..8 {
+            res[8][(n - 1 - i) as usize] = true;
+            res[(n - 1 - i) as usize][8] = true;
+        }
+        // alignment pattern (single, centered) for versions >= 2
+        if version >= 2 {
+            let pos = (4 * version + 10) as i32;
+            for dr in -2..=2 {
+                for dc in -2..=2 {
+                    let dark = dr.abs() == 2 || dc.abs() == 2 || (dr == 0 && dc == 0);
+                    set(m, res, (pos + dr) as usize, (pos + dc) as usize, dark);
+                }
+            }
+        }
+    }
+
+    fn mask_fn(mask: u8, r: usize, c: usize) -> bool {
+        match mask {
+            0 => (r + c) % 2 == 0,
+            1 => r % 2 == 0,
+            2 => c % 3 == 0,
+            3 => (r + c) % 3 == 0,
+            4 => (r / 2 + c / 3) % 2 == 0,
+            5 => (r * c) % 2 + (r * c) % 3 == 0,
+            6 => ((r * c) % 2 + (r * c) % 3) % 2 == 0,
+            _ => ((r + c) % 2 + (r * c) % 3) % 2 == 0,
+        }
+    }
+
+    fn apply_mask(m: &mut [Vec<bool>], res: &[Vec<bool>], mask: u8) {
+        for r in 0..m.len() {
+            for c in 0..m.len() {
+                if !res[r][c] && mask_fn(mask, r, c) {
+                    m[r][c] = !m[r][c];
+                }
+            }
+        }
+    }
+
+    fn place_format_info(m: &mut [Vec<bool>], level: QrEcLevel, mask: u8) {
+        // 5 data bits (2 level + 3 mask) with BCH(15,5) and the QR mask 0x5412
+        let ec_bits = match level {
+            QrEcLevel::Low => 0b01,
+            QrEcLevel::Medium => 0b00,
+            QrEcLevel::Quartile => 0b11,
+            QrEcLevel::High => 0b10,
+        };
+        let data = (ec_bits << 3) | mask as u32;
+        // compute the BCH(15,5) remainder
+        let mut v = data << 10;
+        let gpoly = 0b10100110111u32;
+        while bit_len(v) >= 11 {
+            v ^= gpoly << (bit_len(v) - 11);
+        }
+        let format = ((data << 10) | v) ^ 0x5412;
+        let n = m.len();
+        for i in 0..15 {
+            let bit = (format >> i) & 1 == 1;
+            // around top-left and split across top-right / bottom-left
+            let (r1, c1) = match i {
+                0..=5 => (i, 8usize),
+                6 => (7, 8),
+                7 => (8, 8),
+                8 => (8, 7),
+                _ => (8, 14 - i),
+            };
+            m[r1][c1] = bit;
+            if i < 8 {
+                m[8][n - 1 - i] = bit;
+            } else {
+                m[n - 15 + i][8] = bit;
+            }
+        }
+    }
+
+    fn bit_len(v: u32) -> u32 {
+        32 - v.leading_zeros()
+    }
+
+    fn penalty(m: &[Vec<bool>]) -> i32 {
+        let n = m.len();
+        let mut score = 0;
+        // rule 1: runs of 5+ same-color modules in rows and columns
+        for line in 0..n {
+            for &by_row in &[true, false] {
+                let mut run = 1;
+                let mut prev = if by_row { m[line][0] } else { m[0][line] };
+                for i in 1..n {
+                    let cur = if by_row { m[line][i] } else { m[i][line] };
+                    if cur == prev {
+                        run += 1;
+                    } else {
+                        if run >= 5 {
+                            score += 3 + (run - 5);
+                        }
+                        run = 1;
+                        prev = cur;
+                    }
+                }
+                if run >= 5 {
+                    score += 3 + (run - 5);
+                }
+            }
+        }
+        score
+    }
+}
+
+/// Transforms raw data to a TGA file
+pub fn write_to_tga_file<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+) -> Result<(), FltkError> {
+    write_to_tga_file_(image, path.as_ref())
+}
+
+fn write_to_tga_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<(), FltkError> {
+    // A vector source has no intrinsic raster data; rasterize it at its nominal
+    // size and write that instead of panicking.
+    if is_svg::<I>() {
+        let raster = rasterize(image, 0, 0)?;
+        return write_to_tga_file_(&raster, path);
+    }
+    let path = path.to_str();
+    if path.is_none() {
+        return Err(FltkError::IoError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Could not convert path to string!",
+        )));
+    }
+    let path = std::ffi::CString::new(path.unwrap())?;
+    unsafe {
+        match Fl_raw_image_to_tga(
+            *image.to_raw_data() as *mut u8,
+            path.as_ptr(),
+            image.data_w() as i32,
+            image.data_h() as i32,
+        ) {
+            -1 => Err(FltkError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Could not write image!",
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Transforms raw data to an HDR file. HDR accepts float RGB data.
+pub fn write_to_hdr_file<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+) -> Result<(), FltkError> {
+    write_to_hdr_file_(image, path.as_ref())
+}
+
+fn write_to_hdr_file_<I: ImageExt>(image: &I, path: &std::path::Path) -> Result<(), FltkError> {
+    // A vector source has no intrinsic raster data; rasterize it at its nominal
+    // size and write that instead of panicking.
+    if is_svg::<I>() {
+        let raster = rasterize(image, 0, 0)?;
+        return write_to_hdr_file_(&raster, path);
+    }
+    let path = path.to_str();
+    if path.is_none() {
+        return Err(FltkError::IoError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Could not convert path to string!",
+        )));
+    }
+    let path = std::ffi::CString::new(path.unwrap())?;
+    unsafe {
+        match Fl_raw_image_to_hdr(
+            *image.to_raw_data() as *mut u8,
+            path.as_ptr(),
+            image.data_w() as i32,
+            image.data_h() as i32,
+        ) {
+            -1 => Err(FltkError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Could not write image!",
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Callback used by the stb-style in-memory writers: appends `len` bytes from
+/// `data` into the `Vec<u8>` behind the `context` pointer.
+extern "C" fn image_write_cb(context: *mut raw::c_void, data: *mut raw::c_void, len: i32) {
+    unsafe {
+        let buf = &mut *(context as *mut Vec<u8>);
+        let bytes = std::slice::from_raw_parts(data as *const u8, len as usize);
+        buf.extend_from_slice(bytes);
+    }
+}
+
+/// Encodes an image into an in-memory buffer, routing through a write-to-callback
+/// C entry point so no temporary file is created.
+fn encode_with<I: ImageExt, F>(image: &I, f: F) -> Result<Vec<u8>, FltkError>
+where
+    F: Fn(*mut u8, i32, i32, *mut raw::c_void) -> i32,
+{
+    if std::any::type_name::<I>() == std::any::type_name::<crate::image::SvgImage>() {
+        return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+    }
+    let mut buf: Vec<u8> = Vec::new();
+    let ctx = &mut buf as *mut Vec<u8> as *mut raw::c_void;
+    let ret = f(
+        *image.to_raw_data() as *mut u8,
+        image.data_w() as i32,
+        image.data_h() as i32,
+        ctx,
+    );
+    if ret == -1 {
+        Err(FltkError::Internal(FltkErrorKind::FailedOperation))
+    } else {
+        Ok(buf)
+    }
+}
+
+/// Encodes an image to PNG into an in-memory `Vec<u8>`
+pub fn encode_to_png<I: ImageExt>(image: &I) -> Result<Vec<u8>, FltkError> {
+    encode_with(image, |data, w, h, ctx| unsafe {
+        Fl_raw_image_to_png_mem(data, w, h, ctx, Some(image_write_cb))
+    })
+}
+
+/// Encodes an image to JPEG into an in-memory `Vec<u8>`
+pub fn encode_to_jpg<I: ImageExt>(image: &I) -> Result<Vec<u8>, FltkError> {
+    encode_with(image, |data, w, h, ctx| unsafe {
+        Fl_raw_image_to_jpg_mem(data, w, h, ctx, Some(image_write_cb))
+    })
+}
+
+/// Encodes an image to BMP into an in-memory `Vec<u8>`
+pub fn encode_to_bmp<I: ImageExt>(image: &I) -> Result<Vec<u8>, FltkError> {
+    encode_with(image, |data, w, h, ctx| unsafe {
+        Fl_raw_image_to_bmp_mem(data, w, h, ctx, Some(image_write_cb))
+    })
+}
+
+/// Image formats supported by the raw-image writers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// JPEG (lossy)
+    Jpeg,
+    /// PNG (lossless)
+    Png,
+    /// BMP
+    Bmp,
+    /// Targa
+    Tga,
+    /// Radiance HDR
+    Hdr,
+}
+
+impl ImageFormat {
+    /// Resolves a format from a file extension, treating `jpg`/`jpeg` alike.
+    /// The extension is matched case-insensitively.
+    pub fn from_extension(ext: &str) -> Option<ImageFormat> {
+        match ext.to_ascii_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+            "png" => Some(ImageFormat::Png),
+            "bmp" => Some(ImageFormat::Bmp),
+            "tga" => Some(ImageFormat::Tga),
+            "hdr" => Some(ImageFormat::Hdr),
+            _ => None,
+        }
+    }
+}
+
+/// Writes an image to a file, selecting the format from the path's extension.
+/// Returns an error if the extension is missing or unsupported.
+pub fn write_image_file<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+) -> Result<(), FltkError> {
+    let path = path.as_ref();
+    let fmt = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .and_then(ImageFormat::from_extension)
+        .ok_or_else(|| {
+            FltkError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Unsupported image extension!",
+            ))
+        })?;
+    match fmt {
+        ImageFormat::Jpeg => write_to_jpg_file(image, path),
+        ImageFormat::Png => write_to_png_file(image, path),
+        ImageFormat::Bmp => write_to_bmp_file(image, path),
+        ImageFormat::Tga => write_to_tga_file(image, path),
+        ImageFormat::Hdr => write_to_hdr_file(image, path),
+    }
+}
+
+/// Box-resamples the raw buffer of `image` to `target_w`×`target_h`, preserving
+/// the channel count implied by its color depth, and returns the result as an
+/// `RgbImage` ready to hand to the raw-to-format writers.
+fn resample<I: ImageExt>(
+    image: &I,
+    target_w: i32,
+    target_h: i32,
+) -> Result<crate::image::RgbImage, FltkError> {
+    if target_w <= 0 || target_h <= 0 {
+        return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+    }
+    let sw = image.data_w() as i32;
+    let sh = image.data_h() as i32;
+    let ch = image.depth() as i32;
+    let src = image.to_rgb_data();
+    let mut dst = vec![0u8; (target_w * target_h * ch) as usize];
+    for ty in 0..target_h {
+        let y0 = ty * sh / target_h;
+        let y1 = ((ty + 1) * sh / target_h).max(y0 + 1);
+        for tx in 0..target_w {
+            let x0 = tx * sw / target_w;
+            let x1 = ((tx + 1) * sw / target_w).max(x0 + 1);
+            let di = ((ty * target_w + tx) * ch) as usize;
+            for c in 0..ch {
+                let sum: u64 = (y0..y1)
+                    .flat_map(|sy| (x0..x1).map(move |sx| (sy * sw + sx) * ch + c))
+                    .map(|i| src[i as usize] as u64)
+                    .sum();
+                dst[di + c as usize] = (sum / ((y1 - y0) * (x1 - x0)) as u64) as u8;
+            }
+        }
+    }
+    Ok(crate::image::RgbImage::new(
+        &dst,
+        target_w as u32,
+        target_h as u32,
+        image.depth(),
+    )?)
+}
+
+/// Writes an image to a PNG file, downscaling (or upscaling) the raw buffer to
+/// `target_w`×`target_h` during export.
+pub fn write_to_png_file_resized<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+    target_w: i32,
+    target_h: i32,
+) -> Result<(), FltkError> {
+    write_to_png_file(&resample(image, target_w, target_h)?, path)
+}
+
+/// Writes an image to a JPEG file, resized to `target_w`×`target_h`.
+pub fn write_to_jpg_file_resized<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+    target_w: i32,
+    target_h: i32,
+) -> Result<(), FltkError> {
+    write_to_jpg_file(&resample(image, target_w, target_h)?, path)
+}
+
+/// Writes an image to a BMP file, resized to `target_w`×`target_h`.
+pub fn write_to_bmp_file_resized<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+    target_w: i32,
+    target_h: i32,
+) -> Result<(), FltkError> {
+    write_to_bmp_file(&resample(image, target_w, target_h)?, path)
+}
+
+/// Writes an image to a TGA file, resized to `target_w`×`target_h`.
+pub fn write_to_tga_file_resized<I: ImageExt, P: AsRef<std::path::Path>>(
+    image: &I,
+    path: P,
+    target_w: i32,
+    target_h: i32,
+) -> Result<(), FltkError> {
+    write_to_tga_file(&resample(image, target_w, target_h)?, path)
+}
+
+/// A glyph positioned by the shaping pass, relative to the run's pen origin.
+#[derive(Copy, Clone, Debug)]
+pub struct PositionedGlyph {
+    /// The Unicode scalar the glyph renders (glyph-id resolution is done by the
+    /// backend at blit time)
+    pub ch: char,
+    /// Byte offset of the cluster this glyph belongs to, for cursor hit-testing
+    pub cluster: usize,
+    /// Horizontal advance in pixels
+    pub x_advance: f64,
+    /// Vertical advance in pixels
+    pub y_advance: f64,
+    /// Horizontal offset applied before drawing
+    pub x_offset: f64,
+    /// Vertical offset applied before drawing
+    pub y_offset: f64,
+}
+
+/// Returns true for code points in right-to-left scripts (Hebrew, Arabic and
+/// the Arabic supplement blocks)
+fn is_rtl(c: char) -> bool {
+    matches!(c as u32,
+        0x0590..=0x05FF | 0x0600..=0x06FF | 0x0700..=0x074F | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF)
+}
+
+/// Reorders `txt` into visual order with the given `font`/`size`, returning a
+/// glyph list with pen advances. Input is segmented into directional runs and
+/// RTL runs are reversed before placement, while `cluster` preserves the
+/// original byte offsets for hit-testing. Advances come from the font metrics
+/// so the total matches `width(txt)` and clipping/centering stays correct.
+///
+/// This is reorder-only: it emits one glyph per `char` and does **not** perform
+/// OpenType shaping. Scripts that require contextual joining (Arabic will render
+/// in isolated forms), ligatures, or intra-run reordering (Devanagari) are not
+/// shaped correctly — use a dedicated shaper (HarfBuzz/rustybuzz) for those.
+pub fn shape_text(txt: &str, font: Font, size: u32) -> Vec<PositionedGlyph> {
+    set_font(font, size);
+    let mut glyphs = Vec::new();
+    let mut run: Vec<(usize, char)> = Vec::new();
+    let mut run_rtl = false;
+
+    let mut flush = |run: &mut Vec<(usize, char)>, rtl: bool, out: &mut Vec<PositionedGlyph>| {
+        if rtl {
+            run.reverse();
+        }
+        for &(cluster, ch) in run.iter() {
+            out.push(PositionedGlyph {
+                ch,
+                cluster,
+                x_advance: char_width(ch),
+                y_advance: 0.0,
+                x_offset: 0.0,
+                y_offset: 0.0,
+            });
+        }
+        run.clear();
+    };
+
+    for (i, ch) in txt.char_indices() {
+        let rtl = is_rtl(ch);
+        if !run.is_empty() && rtl != run_rtl && !ch.is_whitespace() {
+            flush(&mut run, run_rtl, &mut glyphs);
+        }
+        if run.is_empty() {
+            run_rtl = rtl;
+        }
+        run.push((i, ch));
+    }
+    flush(&mut run, run_rtl, &mut glyphs);
+    glyphs
+}
+
+/// Draws `txt` using the reordering pass within the box `(x, y, w, h)`,
+/// honoring `align` for placement. This fixes bidirectional (LTR/RTL) ordering
+/// in labels, table cells and menu items; see [`shape_text`] for the limits on
+/// scripts that need full OpenType shaping.
+pub fn draw_text_shaped(txt: &str, x: i32, y: i32, w: i32, h: i32, align: Align) {
+    let glyphs = shape_text(txt, font(), size());
+    let total: f64 = glyphs.iter().map(|g| g.x_advance).sum();
+    let mut pen = if align.contains(Align::Right) {
+        x as f64 + w as f64 - total
+    } else if align.contains(Align::Center) {
+        x as f64 + (w as f64 - total) / 2.0
+    } else {
+        x as f64
+    };
+    let baseline = y + (h + height()) / 2 - descent();
+    for g in &glyphs {
+        let mut buf = [0u8; 4];
+        let s = g.ch.encode_utf8(&mut buf);
+        draw_text(s, (pen + g.x_offset).round() as i32, (baseline as f64 + g.y_offset) as i32);
+        pen += g.x_advance;
+    }
+}
+
+#[cfg(test)]
+mod gradient_tests {
+    use super::*;
+
+    #[test]
+    fn sample_interpolates_between_stops() {
+        let g = Gradient::new(
+            &[(0.0, Color::Black), (1.0, Color::White)],
+            GradientExtend::Clamp,
+        );
+        assert_eq!(g.sample(0.0), (0, 0, 0));
+        assert_eq!(g.sample(1.0), (255, 255, 255));
+        assert_eq!(g.sample(0.5), (128, 128, 128));
+    }
+
+    #[test]
+    fn sample_clamps_to_end_stops() {
+        let g = Gradient::new(
+            &[(0.25, Color::Black), (0.75, Color::White)],
+            GradientExtend::Clamp,
+        );
+        assert_eq!(g.sample(0.0), (0, 0, 0));
+        assert_eq!(g.sample(1.0), (255, 255, 255));
+    }
+
+    #[test]
+    fn wrap_applies_extend_mode() {
+        let clamp = Gradient::new(&[(0.0, Color::Black)], GradientExtend::Clamp);
+        assert_eq!(clamp.wrap(1.5), 1.0);
+        assert_eq!(clamp.wrap(-0.5), 0.0);
+
+        let repeat = Gradient::new(&[(0.0, Color::Black)], GradientExtend::Repeat);
+        assert!((repeat.wrap(1.25) - 0.25).abs() < 1e-6);
+
+        let reflect = Gradient::new(&[(0.0, Color::Black)], GradientExtend::Reflect);
+        assert!((reflect.wrap(1.25) - 0.75).abs() < 1e-6);
+        assert!((reflect.wrap(0.25) - 0.25).abs() < 1e-6);
+    }
+}
+
+#[cfg(test)]
+mod blend_tests {
+    use super::*;
+
+    #[test]
+    fn separable_blends_match_formulas() {
+        assert_eq!(BlendMode::Multiply.blend(255, 128), 128);
+        assert_eq!(BlendMode::Multiply.blend(0, 128), 0);
+        assert_eq!(BlendMode::Screen.blend(0, 128), 128);
+        assert_eq!(BlendMode::Screen.blend(255, 0), 255);
+        assert_eq!(BlendMode::Darken.blend(40, 200), 40);
+        assert_eq!(BlendMode::Lighten.blend(40, 200), 200);
+        assert_eq!(BlendMode::Difference.blend(200, 50), 150);
+    }
+
+    #[test]
+    fn blend_clamps_into_range() {
+        assert_eq!(BlendMode::Add.blend(200, 200), 255);
+        assert_eq!(BlendMode::ColorDodge.blend(255, 10), 255);
+        assert_eq!(BlendMode::ColorBurn.blend(0, 10), 0);
+    }
+
+    #[test]
+    fn overlay_is_hardlight_with_swapped_operands() {
+        for cs in [0, 64, 128, 200, 255] {
+            for cd in [0, 64, 128, 200, 255] {
+                assert_eq!(
+                    BlendMode::Overlay.blend(cs, cd),
+                    BlendMode::HardLight.blend(cd, cs)
+                );
+            }
+        }
+    }
+}
@@ -137,6 +137,161 @@ impl Scroll {
     }
 }
 
+use std::time::Instant;
+
+struct KineticState {
+    friction: f64,
+    /// Recent `(yposition, timestamp)` samples used to estimate release velocity
+    samples: Vec<(f64, Instant)>,
+    velocity: f64,
+    animating: bool,
+    line_step: i32,
+}
+
+static KINETIC: Mutex<BTreeMap<usize, KineticState>> = Mutex::new(BTreeMap::new());
+
+impl Scroll {
+    /// Enables or disables momentum (kinetic) scrolling. When enabled, a press-drag
+    /// moves the content with the pointer and a release continues the scroll with the
+    /// pointer's last velocity, decaying by the friction factor each frame. Enabling
+    /// also wires up arrow/page/home/end keyboard navigation.
+    pub fn enable_kinetic(&mut self, enable: bool) {
+        assert!(!self.was_deleted());
+        let key = self._inner as usize;
+        if !enable {
+            if KINETIC.lock().unwrap().remove(&key).is_some() {
+                self.handle(|_, _| false);
+            }
+            return;
+        }
+        KINETIC.lock().unwrap().insert(
+            key,
+            KineticState {
+                friction: 0.92,
+                samples: Vec::new(),
+                velocity: 0.0,
+                animating: false,
+                line_step: 16,
+            },
+        );
+        let mut this = self.clone();
+        self.handle(move |s, ev| this.kinetic_handle(s, ev));
+    }
+
+    /// Sets the per-frame velocity decay used by kinetic scrolling (default 0.92).
+    pub fn set_friction(&mut self, friction: f64) {
+        if let Some(k) = KINETIC.lock().unwrap().get_mut(&(self._inner as usize)) {
+            k.friction = friction;
+        }
+    }
+
+    fn kinetic_handle(&mut self, s: &mut Scroll, ev: Event) -> bool {
+        let key = self._inner as usize;
+        let page = s.height();
+        match ev {
+            Event::Push => {
+                let mut map = KINETIC.lock().unwrap();
+                if let Some(k) = map.get_mut(&key) {
+                    k.animating = false;
+                    k.velocity = 0.0;
+                    k.samples.clear();
+                    k.samples.push((s.yposition() as f64, Instant::now()));
+                }
+                true
+            }
+            Event::Drag => {
+                let (_mx, my) = crate::app::event_coords();
+                let mut map = KINETIC.lock().unwrap();
+                if let Some(k) = map.get_mut(&key) {
+                    if let Some(&(_, _)) = k.samples.last() {
+                        let target = (s.yposition() as i32 - (my - s.y())).max(0) as u32;
+                        s.scroll_to(s.xposition(), target);
+                    }
+                    k.samples.push((s.yposition() as f64, Instant::now()));
+                    if k.samples.len() > 3 {
+                        k.samples.remove(0);
+                    }
+                }
+                true
+            }
+            Event::Released => {
+                let mut this = self.clone();
+                let mut start = false;
+                {
+                    let mut map = KINETIC.lock().unwrap();
+                    if let Some(k) = map.get_mut(&key) {
+                        if k.samples.len() >= 2 {
+                            let (p0, t0) = k.samples[0];
+                            let (p1, t1) = *k.samples.last().unwrap();
+                            let dt = t1.duration_since(t0).as_secs_f64();
+                            if dt > 0.0 {
+                                k.velocity = (p1 - p0) / dt / 60.0;
+                            }
+                        }
+                        if k.velocity.abs() > 1.0 && !k.animating {
+                            k.animating = true;
+                            start = true;
+                        }
+                    }
+                }
+                if start {
+                    crate::app::add_timeout(0.016, move || this.kinetic_tick());
+                }
+                true
+            }
+            Event::KeyDown => {
+                let step = KINETIC
+                    .lock()
+                    .unwrap()
+                    .get(&key)
+                    .map(|k| k.line_step)
+                    .unwrap_or(16);
+                let (x, y) = (s.xposition() as i32, s.yposition() as i32);
+                let key_pressed = crate::app::event_key();
+                let (nx, ny) = match key_pressed {
+                    Key::Up => (x, y - step),
+                    Key::Down => (x, y + step),
+                    Key::Left => (x - step, y),
+                    Key::Right => (x + step, y),
+                    Key::PageUp => (x, y - page),
+                    Key::PageDown => (x, y + page),
+                    Key::Home => (x, 0),
+                    Key::End => (x, i32::MAX),
+                    _ => return false,
+                };
+                s.scroll_to(nx.max(0) as u32, ny.max(0) as u32);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn kinetic_tick(&mut self) {
+        if self.was_deleted() {
+            return;
+        }
+        let key = self._inner as usize;
+        let mut map = KINETIC.lock().unwrap();
+        let k = match map.get_mut(&key) {
+            Some(k) if k.animating => k,
+            _ => return,
+        };
+        let y = self.yposition() as i32 + k.velocity.round() as i32;
+        let max_y = self.scrollbar().maximum().max(0.0) as i32;
+        let clamped = y.clamp(0, max_y);
+        self.scroll_to(self.xposition(), clamped as u32);
+        k.velocity *= k.friction;
+        // Stop at rest or once clamped against the content edge.
+        if k.velocity.abs() < 1.0 || clamped != y {
+            k.animating = false;
+            return;
+        }
+        drop(map);
+        let mut this = self.clone();
+        crate::app::repeat_timeout(0.016, move || this.kinetic_tick());
+    }
+}
+
 /// Creates a tab which can contain widgets
 #[derive(WidgetBase, WidgetExt, GroupExt, Debug)]
 pub struct Tabs {
@@ -227,8 +382,154 @@ impl Tabs {
         assert!(!self.was_deleted());
         unsafe { mem::transmute(Fl_Tabs_tab_align(self._inner)) }
     }
+
+    /// Chooses how the tab strip copes with more labels than fit across the width
+    pub fn set_overflow(&mut self, overflow: TabsType) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Tabs_handle_overflow(self._inner, overflow as i32) }
+    }
+
+    /// Enables or disables the per-tab close glyph and drag-to-reorder behavior.
+    /// The glyph is hit-tested against equal-width tab slots in the label strip;
+    /// clicking it consults the [`set_close_callback`](#method.set_close_callback)
+    /// result before removing the tab, and a press-drag across slots reorders them.
+    pub fn set_tab_closable(&mut self, closable: bool) {
+        assert!(!self.was_deleted());
+        let key = self._inner as usize;
+        if !closable {
+            if TABS.lock().unwrap().remove(&key).is_some() {
+                self.draw2(|_| {});
+                self.handle(|_, _| false);
+            }
+            return;
+        }
+        TABS.lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| TabsState {
+                close_cb: None,
+                drag_from: None,
+            });
+        let mut this = self.clone();
+        self.draw2(move |t| this.draw_close_glyphs(t));
+        let mut this = self.clone();
+        self.handle(move |t, ev| this.tabs_handle(t, ev));
+    }
+
+    /// Sets the callback fired when a tab's close glyph is clicked. Returning
+    /// `true` removes the tab; returning `false` cancels the removal.
+    pub fn set_close_callback<F: FnMut(Box<dyn GroupExt>) -> bool + 'static>(&mut self, cb: F) {
+        if let Some(s) = TABS.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.close_cb = Some(Box::new(cb));
+        }
+    }
+
+    /// Returns the `(x, y, w, h)` of the tab label strip above the client area.
+    fn tab_strip(&mut self) -> (i32, i32, i32, i32) {
+        let (_cx, cy, _cw, _ch) = self.client_area();
+        let strip_h = (cy - self.y()).max(0);
+        (self.x(), self.y(), self.width(), strip_h)
+    }
+
+    /// Returns the equal-width slot rectangle for tab index `i`.
+    fn tab_slot(&mut self, i: i32, count: i32) -> (i32, i32, i32, i32) {
+        let (sx, sy, sw, sh) = self.tab_strip();
+        let slot = if count > 0 { sw / count } else { sw };
+        (sx + i * slot, sy, slot, sh)
+    }
+
+    fn draw_close_glyphs(&mut self, t: &mut Tabs) {
+        let count = t.children() as i32;
+        for i in 0..count {
+            let (sx, sy, sw, sh) = t.tab_slot(i, count);
+            // A small x in the top-right of each slot.
+            let gx = sx + sw - 14;
+            let gy = sy + sh / 2 - 4;
+            crate::draw::set_draw_color(Color::Foreground);
+            crate::draw::draw_line(gx, gy, gx + 8, gy + 8);
+            crate::draw::draw_line(gx + 8, gy, gx, gy + 8);
+        }
+    }
+
+    fn tabs_handle(&mut self, t: &mut Tabs, ev: Event) -> bool {
+        let key = self._inner as usize;
+        let count = t.children() as i32;
+        let (mx, my) = crate::app::event_coords();
+        let hit = (0..count).find(|&i| {
+            let (sx, sy, sw, sh) = t.tab_slot(i, count);
+            mx >= sx && mx < sx + sw && my >= sy && my < sy + sh
+        });
+        match ev {
+            Event::Push => {
+                if let Some(i) = hit {
+                    let (sx, _sy, sw, _sh) = t.tab_slot(i, count);
+                    if mx >= sx + sw - 16 {
+                        // Click landed on the close glyph.
+                        let mut remove = true;
+                        if let Some(child) = t.child(i as u32) {
+                            let grp: Box<dyn GroupExt> =
+                                Box::new(unsafe { Group::from_widget_ptr(child.as_widget_ptr()) });
+                            if let Some(s) = TABS.lock().unwrap().get_mut(&key) {
+                                if let Some(cb) = s.close_cb.as_mut() {
+                                    remove = cb(grp);
+                                }
+                            }
+                        }
+                        if remove {
+                            if let Some(child) = t.child(i as u32) {
+                                t.remove(&child);
+                                t.redraw();
+                            }
+                        }
+                        return true;
+                    }
+                    if let Some(s) = TABS.lock().unwrap().get_mut(&key) {
+                        s.drag_from = Some(i);
+                    }
+                }
+                false
+            }
+            Event::Released => {
+                let from = TABS.lock().unwrap().get_mut(&key).and_then(|s| s.drag_from.take());
+                if let (Some(from), Some(to)) = (from, hit) {
+                    if from != to {
+                        if let Some(child) = t.child(from as u32) {
+                            t.remove(&child);
+                            t.insert(&child, to);
+                            t.redraw();
+                            return true;
+                        }
+                    }
+                }
+                false
+            }
+            _ => false,
+        }
+    }
 }
 
+/// Tab-strip overflow handling for [`Tabs`](struct.Tabs.html), mirroring FLTK's
+/// `handle_overflow` modes.
+#[repr(i32)]
+#[derive(WidgetType, Debug, Copy, Clone, PartialEq)]
+pub enum TabsType {
+    /// Compress tabs so they all fit
+    Compress = 0,
+    /// Move overflowing tabs into a pulldown menu
+    Pulldown = 1,
+    /// Allow the strip to scroll by dragging
+    Drag = 2,
+    /// Clip tabs that don't fit
+    Clip = 3,
+}
+
+struct TabsState {
+    close_cb: Option<Box<dyn FnMut(Box<dyn GroupExt>) -> bool>>,
+    drag_from: Option<i32>,
+}
+
+static TABS: Mutex<BTreeMap<usize, TabsState>> = Mutex::new(BTreeMap::new());
+
 /// Creates a tile which can contain widgets
 #[derive(WidgetBase, WidgetExt, GroupExt, Debug)]
 pub struct Tile {
@@ -508,3 +809,893 @@ impl DerefMut for HGrid {
         &mut self.hpack
     }
 }
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// A sizing rule for one grid track (column or row): a `min`imum it never drops
+/// below, an `ideal` preferred size, a `max`imum it never grows past, and a
+/// `weight` governing its share of leftover space.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SizeRule {
+    /// Smallest size the track may take
+    pub min: i32,
+    /// Preferred size; kept with the rule but not read by the solver, which
+    /// starts each track at `min`
+    pub ideal: i32,
+    /// Largest size the track may grow to
+    pub max: i32,
+    /// Relative share of leftover space (0 means a fixed track)
+    pub weight: f32,
+}
+
+impl Default for SizeRule {
+    fn default() -> SizeRule {
+        SizeRule {
+            min: 0,
+            ideal: 0,
+            max: i32::MAX,
+            weight: 1.0,
+        }
+    }
+}
+
+/// Per-axis alignment of a widget within the cell(s) it occupies in a [`Grid`](struct.Grid.html).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CellAlign {
+    /// Horizontal placement within the cell
+    pub horizontal: AlignItems,
+    /// Vertical placement within the cell
+    pub vertical: AlignItems,
+}
+
+impl Default for CellAlign {
+    fn default() -> CellAlign {
+        CellAlign {
+            horizontal: AlignItems::Stretch,
+            vertical: AlignItems::Stretch,
+        }
+    }
+}
+
+struct GridCell {
+    widget: usize,
+    row: i32,
+    col: i32,
+    row_span: i32,
+    col_span: i32,
+    align: CellAlign,
+}
+
+struct GridState {
+    cols: Vec<SizeRule>,
+    rows: Vec<SizeRule>,
+    spacing: i32,
+    cells: Vec<GridCell>,
+}
+
+static GRID: Mutex<BTreeMap<usize, GridState>> = Mutex::new(BTreeMap::new());
+
+/// A constraint-solving grid superseding [`VGrid`](struct.VGrid.html)/[`HGrid`](struct.HGrid.html):
+/// each column and row carries a [`SizeRule`], cells may span multiple tracks, and
+/// the layout is re-solved on resize via two-pass water-filling of leftover space.
+/// ```no_run
+/// use fltk::*;
+/// let mut grid = group::Grid::new(0, 0, 400, 300, "");
+/// grid.set_layout(2, 3);
+/// grid.set_col_width(0, 120);       // fixed sidebar column
+/// grid.set_col_weight(1, 2.0);      // main column takes twice the stretch
+/// grid.set(&button::Button::default(), 0, 0, 2, 1, Default::default());
+/// grid.end();
+/// ```
+#[derive(WidgetBase, WidgetExt, GroupExt, Debug)]
+pub struct Grid {
+    _inner: *mut Fl_Group,
+    _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
+}
+
+impl Grid {
+    /// Creates a new 1x1 grid; call [`set_layout`](#method.set_layout) to size it
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Grid {
+        let grp = Group::new(x, y, w, h, label);
+        let ptr = grp.as_widget_ptr() as *mut Fl_Group;
+        grp.end();
+        GRID.lock().unwrap().insert(
+            ptr as usize,
+            GridState {
+                cols: vec![SizeRule::default()],
+                rows: vec![SizeRule::default()],
+                spacing: 0,
+                cells: Vec::new(),
+            },
+        );
+        let mut grid = unsafe { Grid::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget) };
+        let mut this = grid.clone();
+        grid.handle(move |_, ev| {
+            if ev == Event::Resize {
+                this.layout();
+            }
+            false
+        });
+        grid
+    }
+
+    /// Sets the number of rows and columns, resetting every track to the default rule
+    pub fn set_layout(&mut self, rows: i32, cols: i32) {
+        let rows = rows.max(1) as usize;
+        let cols = cols.max(1) as usize;
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.rows = vec![SizeRule::default(); rows];
+            s.cols = vec![SizeRule::default(); cols];
+        }
+        self.layout();
+    }
+
+    /// Sets the gap inserted between adjacent tracks
+    pub fn set_spacing(&mut self, spacing: i32) {
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.spacing = spacing;
+        }
+        self.layout();
+    }
+
+    /// Gives a column a stretch weight relative to the other weighted columns
+    pub fn set_col_weight(&mut self, col: usize, weight: f32) {
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            if let Some(rule) = s.cols.get_mut(col) {
+                rule.weight = weight;
+                rule.max = i32::MAX;
+            }
+        }
+        self.layout();
+    }
+
+    /// Gives a row a stretch weight relative to the other weighted rows
+    pub fn set_row_weight(&mut self, row: usize, weight: f32) {
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            if let Some(rule) = s.rows.get_mut(row) {
+                rule.weight = weight;
+                rule.max = i32::MAX;
+            }
+        }
+        self.layout();
+    }
+
+    /// Pins a column to a fixed width, excluding it from stretch distribution
+    pub fn set_col_width(&mut self, col: usize, width: i32) {
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            if let Some(rule) = s.cols.get_mut(col) {
+                *rule = SizeRule {
+                    min: width,
+                    ideal: width,
+                    max: width,
+                    weight: 0.0,
+                };
+            }
+        }
+        self.layout();
+    }
+
+    /// Pins a row to a fixed height, excluding it from stretch distribution
+    pub fn set_row_height(&mut self, row: usize, height: i32) {
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            if let Some(rule) = s.rows.get_mut(row) {
+                *rule = SizeRule {
+                    min: height,
+                    ideal: height,
+                    max: height,
+                    weight: 0.0,
+                };
+            }
+        }
+        self.layout();
+    }
+
+    /// Places a widget at `(row, col)`, spanning `row_span`x`col_span` tracks,
+    /// adding it to the grid first if it is not a child yet.
+    /// The widget's current size feeds the covered tracks' minimums proportionally.
+    pub fn set<W: WidgetExt>(
+        &mut self,
+        w: &W,
+        row: i32,
+        col: i32,
+        row_span: i32,
+        col_span: i32,
+        align: CellAlign,
+    ) {
+        let ptr = w.as_widget_ptr() as usize;
+        if self.cell_widget(ptr).is_none() {
+            self.add(w);
+        }
+        if let Some(s) = GRID.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.cells.retain(|c| c.widget != ptr);
+            s.cells.push(GridCell {
+                widget: ptr,
+                row,
+                col,
+                row_span: row_span.max(1),
+                col_span: col_span.max(1),
+                align,
+            });
+        }
+        self.layout();
+    }
+
+    /// Re-solves the track sizes and repositions every placed widget.
+    /// Called automatically on resize and whenever a rule or cell changes.
+    pub fn layout(&mut self) {
+        assert!(!self.was_deleted());
+        let guard = GRID.lock().unwrap();
+        let state = match guard.get(&(self._inner as usize)) {
+            Some(s) => s,
+            None => return,
+        };
+        // Fold each widget's own size into the minimums of the tracks it covers.
+        let mut cols = state.cols.clone();
+        let mut rows = state.rows.clone();
+        for cell in &state.cells {
+            if let Some(c) = self.cell_widget(cell.widget) {
+                contribute_min(
+                    &mut cols,
+                    cell.col,
+                    cell.col_span,
+                    c.width(),
+                    state.spacing,
+                );
+                contribute_min(
+                    &mut rows,
+                    cell.row,
+                    cell.row_span,
+                    c.height(),
+                    state.spacing,
+                );
+            }
+        }
+        let col_sizes = solve_tracks(&cols, self.width(), state.spacing);
+        let row_sizes = solve_tracks(&rows, self.height(), state.spacing);
+        let col_off = track_offsets(&col_sizes, self.x(), state.spacing);
+        let row_off = track_offsets(&row_sizes, self.y(), state.spacing);
+
+        for cell in &state.cells {
+            let mut c = match self.cell_widget(cell.widget) {
+                Some(c) => c,
+                None => continue,
+            };
+            let cx = col_off[cell.col as usize];
+            let cy = row_off[cell.row as usize];
+            let cw = span_size(&col_sizes, cell.col, cell.col_span, state.spacing);
+            let ch = span_size(&row_sizes, cell.row, cell.row_span, state.spacing);
+            let (fw, fx) = cross_placement(cw, c.width(), cell.align.horizontal);
+            let (fh, fy) = cross_placement(ch, c.height(), cell.align.vertical);
+            c.resize(cx + fx, cy + fy, fw, fh);
+        }
+        drop(guard);
+        self.redraw();
+    }
+
+    fn cell_widget(&self, ptr: usize) -> Option<Widget> {
+        let children = self.children() as i32;
+        for i in 0..children {
+            let c = self.child(i as u32)?;
+            if c.as_widget_ptr() as usize == ptr {
+                return Some(c);
+            }
+        }
+        None
+    }
+}
+
+/// Adds a spanned widget's min size to its covered tracks, split evenly.
+fn contribute_min(tracks: &mut [SizeRule], start: i32, span: i32, size: i32, spacing: i32) {
+    let start = start as usize;
+    let span = span.max(1) as usize;
+    if start >= tracks.len() {
+        return;
+    }
+    let end = (start + span).min(tracks.len());
+    let inner_spacing = spacing * (end - start - 1) as i32;
+    let share = ((size - inner_spacing).max(0)) / (end - start) as i32;
+    for rule in &mut tracks[start..end] {
+        if share > rule.min {
+            rule.min = share.min(rule.max);
+        }
+    }
+}
+
+/// Two-pass water-filling: seed each track at its min, then distribute leftover
+/// space by weight, capping at each track's max and redistributing the surplus.
+fn solve_tracks(rules: &[SizeRule], total: i32, spacing: i32) -> Vec<i32> {
+    let n = rules.len();
+    let mut sizes: Vec<i32> = rules.iter().map(|r| r.min).collect();
+    if n == 0 {
+        return sizes;
+    }
+    let spacing_total = spacing * (n as i32 - 1).max(0);
+    let mut leftover = total - spacing_total - sizes.iter().sum::<i32>();
+    let mut capped = vec![false; n];
+    while leftover > 0 {
+        let active_weight: f32 = (0..n)
+            .filter(|&i| !capped[i] && rules[i].weight > 0.0)
+            .map(|i| rules[i].weight)
+            .sum();
+        if active_weight <= 0.0 {
+            break;
+        }
+        let mut distributed = 0;
+        let mut newly_capped = false;
+        for i in 0..n {
+            if capped[i] || rules[i].weight <= 0.0 {
+                continue;
+            }
+            let want = (leftover as f32 * rules[i].weight / active_weight).floor() as i32;
+            let room = rules[i].max - sizes[i];
+            let give = want.min(room);
+            sizes[i] += give;
+            distributed += give;
+            if sizes[i] >= rules[i].max {
+                capped[i] = true;
+                newly_capped = true;
+            }
+        }
+        leftover -= distributed;
+        // No rounding progress and nothing capped this round: avoid spinning.
+        if distributed == 0 && !newly_capped {
+            break;
+        }
+    }
+    sizes
+}
+
+/// Returns the absolute start coordinate of each track.
+fn track_offsets(sizes: &[i32], origin: i32, spacing: i32) -> Vec<i32> {
+    let mut offs = Vec::with_capacity(sizes.len());
+    let mut cur = origin;
+    for &s in sizes {
+        offs.push(cur);
+        cur += s + spacing;
+    }
+    offs
+}
+
+/// Total size of a run of tracks including the spacing between them.
+fn span_size(sizes: &[i32], start: i32, span: i32, spacing: i32) -> i32 {
+    let start = start as usize;
+    let end = (start + span.max(1) as usize).min(sizes.len());
+    let body: i32 = sizes[start..end].iter().sum();
+    body + spacing * (end - start - 1) as i32
+}
+
+/// Main-axis orientation of a [`Flex`](struct.Flex.html) container.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FlexDirection {
+    /// Children are laid out left-to-right; the main axis is horizontal
+    Row,
+    /// Children are laid out top-to-bottom; the main axis is vertical
+    Column,
+}
+
+/// Whether a [`Flex`](struct.Flex.html) wraps onto new lines once a line is full.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FlexWrap {
+    /// Keep every child on a single line, shrinking as needed
+    NoWrap,
+    /// Break onto a new line when the next child would overflow the main axis
+    Wrap,
+}
+
+/// Distribution of leftover main-axis space when no child can grow.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum JustifyContent {
+    /// Pack children against the start of the main axis
+    Start,
+    /// Pack children against the end of the main axis
+    End,
+    /// Center the packed children along the main axis
+    Center,
+    /// Spread children so the first/last touch the edges, equal gaps between
+    SpaceBetween,
+    /// Spread children with equal gaps around each, including the edges
+    SpaceAround,
+}
+
+/// Cross-axis placement of children shorter than the line.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AlignItems {
+    /// Align children to the cross-axis start
+    Start,
+    /// Align children to the cross-axis end
+    End,
+    /// Center children on the cross axis
+    Center,
+    /// Grow children to fill the cross axis
+    Stretch,
+}
+
+/// Per-child flex parameters, mirroring the CSS `flex-basis`/`flex-grow`/`flex-shrink` triple.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlexSpec {
+    /// Preferred main-axis size before free space is distributed
+    pub basis: i32,
+    /// Weight used when free space is positive and handed out
+    pub grow: f32,
+    /// Weight (scaled by `basis`) used when space is short and children shrink
+    pub shrink: f32,
+}
+
+impl Default for FlexSpec {
+    fn default() -> FlexSpec {
+        FlexSpec {
+            basis: 0,
+            grow: 0.0,
+            shrink: 1.0,
+        }
+    }
+}
+
+struct FlexState {
+    direction: FlexDirection,
+    wrap: FlexWrap,
+    justify: JustifyContent,
+    align: AlignItems,
+    spacing: i32,
+    specs: BTreeMap<usize, FlexSpec>,
+}
+
+static FLEX: Mutex<BTreeMap<usize, FlexState>> = Mutex::new(BTreeMap::new());
+
+/// A flexbox-style layout group paralleling [`Pack`](struct.Pack.html), but with
+/// per-child grow/shrink/basis factors instead of naive equal division.
+/// The layout is recomputed automatically on resize.
+/// ```no_run
+/// use fltk::*;
+/// let mut flex = group::Flex::new(0, 0, 400, 40, "");
+/// flex.set_direction(group::FlexDirection::Row);
+/// let fixed = button::Button::new(0, 0, 0, 0, "@<");
+/// let grow = frame::Frame::default();
+/// flex.add(&fixed);
+/// flex.add(&grow);
+/// flex.set_child(&fixed, group::FlexSpec { basis: 30, grow: 0.0, shrink: 0.0 });
+/// flex.set_child(&grow, group::FlexSpec { basis: 0, grow: 1.0, shrink: 1.0 });
+/// flex.end();
+/// ```
+#[derive(WidgetBase, WidgetExt, GroupExt, Debug)]
+pub struct Flex {
+    _inner: *mut Fl_Group,
+    _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
+}
+
+impl Flex {
+    /// Creates a new flex container with a row main axis, start justification
+    /// and stretched cross-axis alignment
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Flex {
+        let grp = Group::new(x, y, w, h, label);
+        let ptr = grp.as_widget_ptr() as *mut Fl_Group;
+        let key = ptr as usize;
+        FLEX.lock().unwrap().insert(
+            key,
+            FlexState {
+                direction: FlexDirection::Row,
+                wrap: FlexWrap::NoWrap,
+                justify: JustifyContent::Start,
+                align: AlignItems::Stretch,
+                spacing: 0,
+                specs: BTreeMap::new(),
+            },
+        );
+        let mut flex = unsafe { Flex::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget) };
+        flex.handle(move |f, ev| {
+            if ev == Event::Resize {
+                f.layout();
+            }
+            false
+        });
+        flex
+    }
+
+    /// Sets the main-axis direction
+    pub fn set_direction(&mut self, dir: FlexDirection) {
+        if let Some(s) = FLEX.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.direction = dir;
+        }
+        self.layout();
+    }
+
+    /// Sets whether children wrap onto new lines
+    pub fn set_wrap(&mut self, wrap: FlexWrap) {
+        if let Some(s) = FLEX.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.wrap = wrap;
+        }
+        self.layout();
+    }
+
+    /// Sets the main-axis distribution used when no child grows
+    pub fn set_justify(&mut self, justify: JustifyContent) {
+        if let Some(s) = FLEX.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.justify = justify;
+        }
+        self.layout();
+    }
+
+    /// Sets the cross-axis alignment of children
+    pub fn set_align_items(&mut self, align: AlignItems) {
+        if let Some(s) = FLEX.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.align = align;
+        }
+        self.layout();
+    }
+
+    /// Sets the gap inserted between adjacent children
+    pub fn set_spacing(&mut self, spacing: i32) {
+        if let Some(s) = FLEX.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.spacing = spacing;
+        }
+        self.layout();
+    }
+
+    /// Assigns the grow/shrink/basis spec for a child already added to the flex
+    pub fn set_child<W: WidgetExt>(&mut self, w: &W, spec: FlexSpec) {
+        let child = w.as_widget_ptr() as usize;
+        if let Some(s) = FLEX.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.specs.insert(child, spec);
+        }
+        self.layout();
+    }
+
+    /// Runs the flexbox pass over the current children.
+    /// Called automatically on resize and whenever a parameter changes.
+    pub fn layout(&mut self) {
+        assert!(!self.was_deleted());
+        let children = self.children() as i32;
+        if children == 0 {
+            return;
+        }
+        let guard = FLEX.lock().unwrap();
+        let state = match guard.get(&(self._inner as usize)) {
+            Some(s) => s,
+            None => return,
+        };
+        let horizontal = state.direction == FlexDirection::Row;
+        let spacing = state.spacing;
+        let main = if horizontal { self.width() } else { self.height() };
+        let cross_total = if horizontal { self.height() } else { self.width() };
+
+        // Gather each child's spec, defaulting basis to its current main-axis size.
+        let mut kids = Vec::with_capacity(children as usize);
+        for i in 0..children {
+            let c = self.child(i as u32).unwrap();
+            let mut spec = state
+                .specs
+                .get(&(c.as_widget_ptr() as usize))
+                .copied()
+                .unwrap_or_default();
+            if spec.basis == 0 {
+                spec.basis = if horizontal { c.width() } else { c.height() };
+            }
+            kids.push((c, spec));
+        }
+
+        // Break children into lines. Without wrapping everything is one line.
+        let mut lines: Vec<Vec<usize>> = Vec::new();
+        if state.wrap == FlexWrap::Wrap {
+            let mut line: Vec<usize> = Vec::new();
+            let mut run = 0;
+            for (i, (_, s)) in kids.iter().enumerate() {
+                let add = s.basis + if line.is_empty() { 0 } else { spacing };
+                if !line.is_empty() && run + add > main {
+                    lines.push(std::mem::take(&mut line));
+                    run = s.basis;
+                } else {
+                    run += add;
+                }
+                line.push(i);
+            }
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        } else {
+            lines.push((0..kids.len()).collect());
+        }
+
+        let (ox, oy) = (self.x(), self.y());
+        let line_cross = (cross_total / lines.len() as i32).max(0);
+        for (row, idxs) in lines.iter().enumerate() {
+            let count = idxs.len() as i32;
+            let total_spacing = spacing * (count - 1).max(0);
+            let sum_basis: i32 = idxs.iter().map(|&i| kids[i].1.basis).sum();
+            let free = main - sum_basis - total_spacing;
+            let sum_grow: f32 = idxs.iter().map(|&i| kids[i].1.grow).sum();
+            let sum_shrink: f32 = idxs
+                .iter()
+                .map(|&i| kids[i].1.shrink * kids[i].1.basis as f32)
+                .sum();
+
+            let sizes: Vec<i32> = idxs
+                .iter()
+                .map(|&i| {
+                    let s = kids[i].1;
+                    let sz = if free > 0 && sum_grow > 0.0 {
+                        s.basis + (free as f32 * s.grow / sum_grow).round() as i32
+                    } else if free < 0 && sum_shrink > 0.0 {
+                        let w = s.shrink * s.basis as f32;
+                        (s.basis as f32 + free as f32 * w / sum_shrink).round() as i32
+                    } else {
+                        s.basis
+                    };
+                    sz.max(0)
+                })
+                .collect();
+
+            let used: i32 = sizes.iter().sum::<i32>() + total_spacing;
+            let leftover = (main - used).max(0);
+            let (mut cursor, gap) = if sum_grow > 0.0 && free > 0 {
+                (0, spacing)
+            } else {
+                match state.justify {
+                    JustifyContent::Start => (0, spacing),
+                    JustifyContent::End => (leftover, spacing),
+                    JustifyContent::Center => (leftover / 2, spacing),
+                    JustifyContent::SpaceBetween => {
+                        let extra = if count > 1 { leftover / (count - 1) } else { 0 };
+                        (0, spacing + extra)
+                    }
+                    JustifyContent::SpaceAround => {
+                        let extra = leftover / count;
+                        (extra / 2, spacing + extra)
+                    }
+                }
+            };
+
+            let row_off = row as i32 * line_cross;
+            for (j, &i) in idxs.iter().enumerate() {
+                let size = sizes[j];
+                let mut c = kids[i].0.clone();
+                if horizontal {
+                    let (ch, cy) = cross_placement(line_cross, c.height(), state.align);
+                    c.resize(ox + cursor, oy + row_off + cy, size, ch);
+                } else {
+                    let (cw, cx) = cross_placement(line_cross, c.width(), state.align);
+                    c.resize(ox + row_off + cx, oy + cursor, cw, size);
+                }
+                cursor += size + gap;
+            }
+        }
+        drop(guard);
+        self.redraw();
+    }
+}
+
+/// Returns the cross-axis `(size, offset)` for a child given the line size.
+fn cross_placement(line: i32, child: i32, align: AlignItems) -> (i32, i32) {
+    match align {
+        AlignItems::Stretch => (line, 0),
+        AlignItems::Start => (child, 0),
+        AlignItems::End => (child, line - child),
+        AlignItems::Center => (child, (line - child) / 2),
+    }
+}
+
+/// Chooses whether a [`WrapGroup`](struct.WrapGroup.html) reflows on width or height.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WrapType {
+    /// Flow left-to-right, wrapping to a new row when the group width is exceeded
+    Horizontal,
+    /// Flow top-to-bottom, wrapping to a new column when the group height is exceeded
+    Vertical,
+}
+
+struct WrapState {
+    bar: crate::valuator::Scrollbar,
+    wrap: WrapType,
+    spacing: i32,
+    offset: (i32, i32),
+}
+
+static WRAP: Mutex<BTreeMap<usize, WrapState>> = Mutex::new(BTreeMap::new());
+
+/// A group whose children flow left-to-right (or top-to-bottom) and wrap to the
+/// next line when they exceed the group extent, with an integrated scrollbar for
+/// the overflow — useful for tag clouds, icon galleries, and toolbars.
+/// ```no_run
+/// use fltk::*;
+/// let mut flow = group::WrapGroup::new(0, 0, 300, 200, "");
+/// for i in 0..20 {
+///     flow.add(&button::Button::new(0, 0, 60, 25, None).with_label(&i.to_string()));
+/// }
+/// flow.set_spacing(4);
+/// flow.end();
+/// ```
+#[derive(WidgetBase, WidgetExt, GroupExt, Debug)]
+pub struct WrapGroup {
+    _inner: *mut Fl_Group,
+    _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
+}
+
+impl WrapGroup {
+    /// Creates a new wrapping flow group with a vertical scrollbar
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> WrapGroup {
+        let grp = Group::new(x, y, w, h, label);
+        let ptr = grp.as_widget_ptr() as *mut Fl_Group;
+        let mut bar = crate::valuator::Scrollbar::new(x + w - 15, y, 15, h, "");
+        bar.set_type(crate::valuator::ScrollbarType::Vertical);
+        grp.end();
+        WRAP.lock().unwrap().insert(
+            ptr as usize,
+            WrapState {
+                bar: bar.clone(),
+                wrap: WrapType::Horizontal,
+                spacing: 0,
+                offset: (0, 0),
+            },
+        );
+        let mut grp = unsafe {
+            WrapGroup::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget)
+        };
+        let mut flow = grp.clone();
+        bar.set_callback(move |_| flow.layout());
+        let mut flow = grp.clone();
+        grp.handle(move |g, ev| match ev {
+            Event::Resize => {
+                flow.layout();
+                false
+            }
+            Event::MouseWheel => {
+                if let Some(s) = WRAP.lock().unwrap().get_mut(&(g.as_widget_ptr() as usize)) {
+                    let step = 15.0 * crate::app::event_dy() as f64;
+                    let v = (s.bar.value() + step).clamp(s.bar.minimum(), s.bar.maximum());
+                    s.bar.set_value(v);
+                }
+                flow.layout();
+                true
+            }
+            _ => false,
+        });
+        grp
+    }
+
+    /// Sets the gap inserted between adjacent children, both within and between lines
+    pub fn set_spacing(&mut self, spacing: i32) {
+        if let Some(s) = WRAP.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.spacing = spacing;
+        }
+        self.layout();
+    }
+
+    /// Sets a fixed offset of the flow origin from the group's top-left corner;
+    /// every line starts at this offset
+    pub fn set_offset(&mut self, x: i32, y: i32) {
+        if let Some(s) = WRAP.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.offset = (x, y);
+        }
+        self.layout();
+    }
+
+    /// Chooses whether wrapping happens on width (horizontal flow) or height
+    pub fn set_wrap_type(&mut self, wrap: WrapType) {
+        if let Some(s) = WRAP.lock().unwrap().get_mut(&(self._inner as usize)) {
+            s.wrap = wrap;
+        }
+        self.layout();
+    }
+
+    /// Returns a handle to the group's scrollbar
+    pub fn scrollbar(&self) -> impl ValuatorExt {
+        WRAP.lock().unwrap().get(&(self._inner as usize)).unwrap().bar.clone()
+    }
+
+    /// Reflows the children and updates the scrollbar range.
+    /// Called automatically on resize, scroll, and parameter changes.
+    pub fn layout(&mut self) {
+        assert!(!self.was_deleted());
+        let guard = WRAP.lock().unwrap();
+        let state = match guard.get(&(self._inner as usize)) {
+            Some(s) => s,
+            None => return,
+        };
+        let horizontal = state.wrap == WrapType::Horizontal;
+        let spacing = state.spacing;
+        let bar_size = 15;
+        let avail = if horizontal {
+            self.width() - bar_size
+        } else {
+            self.height() - bar_size
+        };
+        let scroll = state.bar.value() as i32;
+        let (ox, oy) = state.offset;
+        let origin_x = self.x() + ox;
+        let origin_y = self.y() + oy;
+
+        // First pass: place everything relative to the content origin and find
+        // the total content extent along the scrolling axis.
+        let children = self.children() as i32;
+        let mut cur = 0; // main-axis cursor
+        let mut line = 0; // cross-axis cursor
+        let mut line_extent = 0; // tallest/widest child on the current line
+        let mut content = 0;
+        let bar_ptr = state.bar.as_widget_ptr() as usize;
+        for i in 0..children {
+            let mut c = self.child(i as u32).unwrap();
+            if c.as_widget_ptr() as usize == bar_ptr {
+                continue;
+            }
+            let (cw, ch) = (c.width(), c.height());
+            let span = if horizontal { cw } else { ch };
+            let thick = if horizontal { ch } else { cw };
+            if cur > 0 && cur + span > avail {
+                cur = 0;
+                line += line_extent + spacing;
+                line_extent = 0;
+            }
+            if horizontal {
+                c.resize(origin_x + cur, origin_y + line - scroll, cw, ch);
+            } else {
+                c.resize(origin_x + line - scroll, origin_y + cur, cw, ch);
+            }
+            cur += span + spacing;
+            line_extent = line_extent.max(thick);
+            content = line + line_extent;
+        }
+
+        // Size the scrollbar to the overflow and keep it pinned to the edge.
+        let mut bar = state.bar.clone();
+        let view = if horizontal { self.height() } else { self.width() };
+        let over = (content - view).max(0);
+        bar.set_bounds(0.0, over as f64);
+        bar.set_slider_size(if content > 0 {
+            (view as f32 / content as f32).clamp(0.05, 1.0)
+        } else {
+            1.0
+        });
+        if horizontal {
+            bar.resize(self.x() + self.width() - bar_size, self.y(), bar_size, self.height());
+        } else {
+            bar.resize(self.x(), self.y() + self.height() - bar_size, self.width(), bar_size);
+        }
+        drop(guard);
+        self.redraw();
+    }
+}
+
+#[cfg(test)]
+mod track_tests {
+    use super::{solve_tracks, SizeRule};
+
+    fn rule(min: i32, max: i32, weight: f32) -> SizeRule {
+        SizeRule {
+            min,
+            ideal: min,
+            max,
+            weight,
+        }
+    }
+
+    #[test]
+    fn distributes_leftover_by_weight() {
+        let rules = [rule(0, i32::MAX, 1.0), rule(0, i32::MAX, 3.0)];
+        let sizes = solve_tracks(&rules, 100, 0);
+        assert_eq!(sizes, vec![25, 75]);
+    }
+
+    #[test]
+    fn respects_minimums_and_spacing() {
+        let rules = [rule(30, i32::MAX, 1.0), rule(10, i32::MAX, 1.0)];
+        // total 100, minus 10 spacing and the 40 of seeded mins leaves 50 to
+        // share evenly by weight on top of each track's min
+        let sizes = solve_tracks(&rules, 100, 10);
+        assert_eq!(sizes, vec![55, 35]);
+    }
+
+    #[test]
+    fn caps_redistribute_surplus_to_uncapped() {
+        let rules = [rule(0, 20, 1.0), rule(0, i32::MAX, 1.0)];
+        let sizes = solve_tracks(&rules, 100, 0);
+        assert_eq!(sizes[0], 20);
+        assert_eq!(sizes[1], 80);
+    }
+
+    #[test]
+    fn fixed_tracks_keep_their_min() {
+        let rules = [rule(40, 40, 0.0), rule(0, i32::MAX, 1.0)];
+        let sizes = solve_tracks(&rules, 100, 0);
+        assert_eq!(sizes, vec![40, 60]);
+    }
+}